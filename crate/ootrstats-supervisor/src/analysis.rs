@@ -0,0 +1,316 @@
+use {
+    std::{
+        collections::BTreeMap,
+        io,
+        path::{
+            Path,
+            PathBuf,
+        },
+        sync::{
+            Arc,
+            Mutex,
+            mpsc as std_mpsc,
+        },
+    },
+    bytes::Bytes,
+    itertools::Itertools as _,
+    ootr_utils::spoiler::SpoilerLog,
+    serde::{
+        Deserialize,
+        Serialize,
+    },
+    tokio::sync::mpsc,
+    wheel::{
+        fs,
+        traits::IoResultExt as _,
+    },
+    ootrstats::SeedIdx,
+    crate::{
+        Error,
+        Subcommand,
+        parse_traceback,
+        query,
+        split_worlds,
+    },
+};
+
+/// A JSON query compiled by [`query::compile`], taking the spoiler log and the seed's metadata as arguments.
+type RunQuery = Box<dyn Fn(jaq_json::Val, jaq_json::Val) -> Result<Vec<jaq_json::Val>, Error>>;
+
+/// What a subcommand needs to know about each successful seed's spoiler log.
+///
+/// Unlike an [`Analysis`], this can be sent to other threads.
+#[derive(Clone)]
+pub(crate) enum Spec {
+    /// The outputs of JSON queries, used by the `bisect`, `categorize`, and `find` subcommands.
+    Query {
+        /// The queries to run, along with their names if they were read from a query file.
+        queries: Vec<(Option<String>, String)>,
+        /// The query given to the `categorize` subcommand's `--by` option.
+        by: Option<String>,
+        /// The query given to the `categorize` subcommand's `--where` option.
+        filter: Option<String>,
+        per_world: bool,
+        /// Whether failed seeds are also analyzed, using their error logs as input.
+        include_failures: bool,
+    },
+    /// The chest appearances in Mido's house, used by the `midos-house` subcommand.
+    MidosHouse,
+}
+
+impl Spec {
+    /// The analysis required by the given subcommand, or `None` if it doesn't look at spoiler logs.
+    pub(crate) async fn new(subcommand: Option<&Subcommand>) -> Result<Option<Self>, Error> {
+        Ok(Some(match subcommand {
+            None | Some(Subcommand::Bench { .. } | Subcommand::BenchHistory { .. } | Subcommand::Failures { .. } | Subcommand::Repro { .. } | Subcommand::WorldCounts { .. } | Subcommand::Bisect { query: None, .. }) => return Ok(None),
+            Some(Subcommand::MidosHouse { .. }) => Self::MidosHouse,
+            Some(Subcommand::Categorize { query, query_files, by, filter, per_world, include_failures }) => {
+                let mut queries = query.iter().map(|query| (None, query.clone())).collect_vec();
+                for path in query_files {
+                    let named_queries = if path.extension().is_some_and(|ext| ext == "toml") {
+                        toml::from_str::<BTreeMap<String, String>>(&fs::read_to_string(path).await?)?
+                    } else {
+                        fs::read_json::<BTreeMap<String, String>>(path).await?
+                    };
+                    queries.extend(named_queries.into_iter().map(|(name, query)| (Some(name), query)));
+                }
+                Self::Query { queries, by: by.clone(), filter: filter.clone(), per_world: *per_world, include_failures: *include_failures }
+            }
+            Some(Subcommand::Bisect { query: Some(query), .. } | Subcommand::Find { query, .. }) => Self::Query { queries: vec![(None, query.clone())], by: None, filter: None, per_world: false, include_failures: false },
+        }))
+    }
+
+    /// Identifies this analysis in the `extracted.json` file of a seed, so that values extracted for different queries aren't reused.
+    pub(crate) fn key(&self) -> String {
+        match self {
+            // failed seeds are never stored without their error logs, so include_failures doesn't need to be part of the key
+            Self::Query { queries, by, filter, per_world, include_failures: _ } => format!(
+                "{}queries {}{}{}",
+                if *per_world { "per-world " } else { "" },
+                queries.iter().map(|(name, query)| if let Some(name) = name { format!("{name:?}={query:?}") } else { format!("{query:?}") }).format(" "),
+                if let Some(by) = by { format!(" by {by:?}") } else { String::default() },
+                if let Some(filter) = filter { format!(" where {filter:?}") } else { String::default() },
+            ),
+            Self::MidosHouse => format!("midos-house"),
+        }
+    }
+
+    /// Whether failed seeds are analyzed in addition to successful ones.
+    pub(crate) fn include_failures(&self) -> bool {
+        match self {
+            Self::Query { include_failures, .. } => *include_failures,
+            Self::MidosHouse => false,
+        }
+    }
+}
+
+/// A [`Spec`] with its queries compiled.
+pub(crate) enum Analysis {
+    Query {
+        run_queries: Vec<RunQuery>,
+        run_by: Option<RunQuery>,
+        run_filter: Option<RunQuery>,
+        per_world: bool,
+    },
+    MidosHouse,
+}
+
+impl Analysis {
+    pub(crate) fn new(spec: &Spec) -> Result<Self, Error> {
+        Ok(match spec {
+            Spec::Query { queries, by, filter, per_world, include_failures: _ } => Self::Query {
+                run_queries: queries.iter().map(|(_, query)| Ok::<_, Error>(Box::new(query::compile(query)?) as RunQuery)).try_collect()?,
+                run_by: by.as_deref().map(|by| Ok::<_, Error>(Box::new(query::compile(by)?) as RunQuery)).transpose()?,
+                run_filter: filter.as_deref().map(|filter| Ok::<_, Error>(Box::new(query::compile(filter)?) as RunQuery)).transpose()?,
+                per_world: *per_world,
+            },
+            Spec::MidosHouse => Self::MidosHouse,
+        })
+    }
+
+    /// Extracts the values from a spoiler log (or the input for a failed seed). `meta` is made available to queries as `$meta`.
+    pub(crate) fn extract(&self, spoiler_log: serde_json::Value, meta: serde_json::Value) -> Result<Extracted, Error> {
+        Ok(match self {
+            Self::Query { run_queries, run_by, run_filter, per_world } => {
+                let inputs = if *per_world {
+                    split_worlds(spoiler_log).into_iter().enumerate().map(|(world_idx, world_log)| Ok::<_, Error>((Some(u8::try_from(world_idx + 1)?), world_log))).try_collect::<_, Vec<_>, _>()?
+                } else {
+                    vec![(None, spoiler_log)]
+                };
+                let meta = serde_json::from_value::<jaq_json::Val>(meta)?;
+                let mut world_outputs = Vec::with_capacity(inputs.len());
+                for (world, input) in inputs {
+                    let input = serde_json::from_value::<jaq_json::Val>(input)?;
+                    let run = |run_query: &RunQuery| -> Result<Vec<serde_json::Value>, Error> {
+                        Ok(run_query(input.clone(), meta.clone())?.iter().map(query::to_json).try_collect()?)
+                    };
+                    if let Some(run_filter) = run_filter {
+                        // inputs that don't match the --where query aren't counted at all
+                        if !run(run_filter)?.iter().any(query::is_truthy) { continue }
+                    }
+                    world_outputs.push(WorldOutputs {
+                        outputs: run_queries.iter().map(&run).try_collect()?,
+                        by: run_by.as_ref().map(run).transpose()?,
+                        world,
+                    });
+                }
+                Extracted::Query(world_outputs)
+            }
+            Self::MidosHouse => Extracted::MidosHouse(
+                serde_json::from_value::<SpoilerLog>(spoiler_log)?.midos_house_chests().map(serde_json::to_value).try_collect()?,
+            ),
+        })
+    }
+}
+
+/// The values extracted from a spoiler log by an [`Analysis`].
+#[derive(Deserialize, Serialize)]
+pub(crate) enum Extracted {
+    /// The outputs of the queries for each world, or for `None` if the queries weren't run separately for each world.
+    Query(Vec<WorldOutputs>),
+    MidosHouse(Vec<serde_json::Value>),
+}
+
+impl Extracted {
+    /// Whether a query output a value other than `false` or `null`.
+    pub(crate) fn is_match(&self) -> bool {
+        match self {
+            Self::Query(world_outputs) => world_outputs.iter().any(|WorldOutputs { outputs, .. }| outputs.iter().flatten().any(query::is_truthy)),
+            Self::MidosHouse(_) => false,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct WorldOutputs {
+    pub(crate) world: Option<u8>,
+    /// The outputs of each query, in the order they were given.
+    pub(crate) outputs: Vec<Vec<serde_json::Value>>,
+    /// The outputs of the `--by` query, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) by: Option<Vec<serde_json::Value>>,
+}
+
+/// The contents of the `extracted.json` file which replaces the spoiler log of a seed whose log was discarded.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Stored {
+    /// the key of the analysis the values were extracted for, or `None` if the spoiler log wasn't needed.
+    analysis: Option<String>,
+    pub(crate) extracted: Option<Extracted>,
+}
+
+impl Stored {
+    /// Whether a run with the given analysis can use this seed without its spoiler log.
+    pub(crate) fn is_reusable(&self, analysis_key: Option<&str>) -> bool {
+        analysis_key.is_none_or(|analysis_key| self.analysis.as_deref() == Some(analysis_key))
+    }
+}
+
+/// The value of the `$meta` variable in JSON queries.
+#[derive(Serialize)]
+struct Meta<'a> {
+    seed_idx: SeedIdx,
+    worker: &'a str,
+    instructions: Option<u64>,
+    rsl_instructions: Option<u64>,
+    world_count: Option<u8>,
+    /// the settings chosen by the random settings script, if it was used.
+    random_settings: Option<serde_json::Value>,
+}
+
+/// Collects the metadata of a seed to make it available to JSON queries.
+pub(crate) async fn meta(seed_dir: &Path, seed_idx: SeedIdx, worker: &str, instructions: Option<u64>, rsl_instructions: Option<u64>, world_count: Option<u8>) -> Result<serde_json::Value, Error> {
+    let random_settings = match fs::read_json(seed_dir.join("random_settings.json")).await {
+        Ok(random_settings) => Some(random_settings),
+        Err(wheel::Error::Io { inner, .. }) if inner.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    Ok(serde_json::to_value(Meta { seed_idx, worker, instructions, rsl_instructions, world_count, random_settings })?)
+}
+
+/// The input of JSON queries for a failed seed, if failed seeds are analyzed.
+#[derive(Serialize)]
+struct FailureInput<'a> {
+    error_log: &'a str,
+    traceback: Traceback<'a>,
+}
+
+#[derive(Serialize)]
+struct Traceback<'a> {
+    location: &'a str,
+    message: &'a str,
+}
+
+/// Where to read a spoiler log from.
+pub(crate) enum Source {
+    Path(PathBuf),
+    Bytes(Bytes),
+    /// The input for a failed seed, see [`Source::failure`].
+    Value(serde_json::Value),
+}
+
+impl Source {
+    /// Makes the error log of a failed seed and its parsed traceback the input of the queries.
+    pub(crate) fn failure(error_log: &[u8]) -> Result<Self, Error> {
+        let error_log = std::str::from_utf8(error_log)?;
+        let (location, message) = parse_traceback(error_log);
+        let traceback = Traceback { location, message };
+        Ok(Self::Value(serde_json::to_value(FailureInput { error_log, traceback })?))
+    }
+}
+
+/// Runs an [`Analysis`] on one thread per core, so that spoiler logs are analyzed in parallel and while seeds are still being rolled.
+///
+/// The threads exit once the pool is dropped and all spoiler logs passed to [`Pool::extract`] have been analyzed.
+pub(crate) struct Pool {
+    job_tx: std_mpsc::Sender<(usize, Source, serde_json::Value)>,
+}
+
+impl Pool {
+    /// Starts the threads, which send the values extracted from each spoiler log to `result_tx` along with the slot passed to [`Pool::extract`].
+    pub(crate) fn new(spec: &Spec, result_tx: mpsc::Sender<Result<(usize, Extracted), Error>>) -> Self {
+        let (job_tx, job_rx) = std_mpsc::channel();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        for _ in 0..std::thread::available_parallelism().map_or(1, |num_threads| num_threads.get()) {
+            let spec = spec.clone();
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                // the compiled query can't be shared between threads, so each thread compiles its own
+                let analysis = match Analysis::new(&spec) {
+                    Ok(analysis) => analysis,
+                    Err(e) => {
+                        let _ = result_tx.blocking_send(Err(e));
+                        return
+                    }
+                };
+                loop {
+                    let Ok((slot, source, meta)) = job_rx.lock().expect("analysis thread panicked").recv() else { break };
+                    let res = match source {
+                        Source::Path(path) => std::fs::read(&path).at(&path).map_err(Error::from).and_then(|spoiler_log| Ok(serde_json::from_slice::<serde_json::Value>(&spoiler_log)?)),
+                        Source::Bytes(bytes) => serde_json::from_slice::<serde_json::Value>(&bytes).map_err(Error::from),
+                        Source::Value(value) => Ok(value),
+                    }.and_then(|input| analysis.extract(input, meta));
+                    if result_tx.blocking_send(res.map(|extracted| (slot, extracted))).is_err() { break }
+                }
+            });
+        }
+        Self { job_tx }
+    }
+
+    /// Queues the given spoiler log to be analyzed. `meta` is made available to queries as `$meta`.
+    pub(crate) fn extract(&self, slot: usize, source: Source, meta: serde_json::Value) {
+        self.job_tx.send((slot, source, meta)).expect("all analysis threads exited");
+    }
+}
+
+/// Replaces the spoiler log (and patch, if any) of a seed with the values extracted from it, keeping the seed's metadata so it's not rolled again.
+pub(crate) async fn discard_log(seed_dir: &Path, analysis_key: Option<&str>, extracted: Option<Extracted>) -> Result<Option<Extracted>, Error> {
+    let stored = Stored { analysis: analysis_key.map(str::to_owned), extracted };
+    fs::write_json(seed_dir.join("extracted.json"), &stored).await?;
+    fs::remove_file(seed_dir.join("spoiler.json")).await?;
+    for ext in ["zpf", "zpfz"] {
+        fs::remove_file(seed_dir.join(format!("patch.{ext}"))).await.missing_ok()?;
+    }
+    Ok(stored.extracted)
+}
@@ -0,0 +1,119 @@
+use crate::{
+    SeedState,
+    analysis::Extracted,
+    stats::{
+        self,
+        Interval,
+    },
+};
+
+/// What makes a revision “bad” for the `bisect` subcommand.
+pub(crate) enum Criterion {
+    /// The fraction of failed seeds is above the given value.
+    FailureRate(f64),
+    /// The fraction of successful seeds for which the query outputs a value other than `false` or `null` is above the given value.
+    Query {
+        frequency: f64,
+    },
+    /// The average total instructions until success are above the given value.
+    Instructions(f64),
+}
+
+/// The observations for a criterion at a single revision.
+pub(crate) enum Sample {
+    Proportion {
+        hits: usize,
+        trials: usize,
+        threshold: f64,
+    },
+    Ratio {
+        /// `(1 or 0 for success or failure, instructions)` for each benchmarked seed.
+        samples: Vec<(f64, f64)>,
+        threshold: f64,
+    },
+}
+
+impl Sample {
+    pub(crate) fn new(criterion: &Criterion, seed_states: Vec<SeedState>) -> Self {
+        match *criterion {
+            Criterion::FailureRate(threshold) => {
+                let mut hits = 0;
+                let mut trials = 0;
+                for state in seed_states {
+                    match state {
+                        SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
+                        SeedState::Cancelled | SeedState::InfraFailure => {}
+                        SeedState::Success { .. } => trials += 1,
+                        SeedState::Failure { .. } => {
+                            hits += 1;
+                            trials += 1;
+                        }
+                    }
+                }
+                Self::Proportion { hits, trials, threshold }
+            }
+            Criterion::Query { frequency } => {
+                let mut hits = 0;
+                let mut trials = 0;
+                for state in seed_states {
+                    if let SeedState::Success { extracted, .. } = state {
+                        trials += 1;
+                        if extracted.as_ref().is_some_and(Extracted::is_match) {
+                            hits += 1;
+                        }
+                    }
+                }
+                Self::Proportion { hits, trials, threshold: frequency }
+            }
+            Criterion::Instructions(threshold) => Self::Ratio {
+                samples: seed_states.into_iter().filter_map(|state| match state {
+                    SeedState::Success { instructions: Some(instructions), .. } => Some((1.0, instructions as f64)),
+                    SeedState::Failure { instructions: Some(instructions), .. } => Some((0.0, instructions as f64)),
+                    _ => None,
+                }).collect(),
+                threshold,
+            },
+        }
+    }
+
+    pub(crate) fn is_proportion(&self) -> bool {
+        matches!(self, Self::Proportion { .. })
+    }
+
+    pub(crate) fn threshold(&self) -> f64 {
+        match *self {
+            Self::Proportion { threshold, .. } | Self::Ratio { threshold, .. } => threshold,
+        }
+    }
+
+    pub(crate) fn interval(&self, confidence: f64) -> Option<Interval> {
+        match *self {
+            Self::Proportion { hits, trials, .. } => stats::wilson_interval(hits, trials, confidence),
+            Self::Ratio { ref samples, .. } => stats::ratio_interval(samples, confidence),
+        }
+    }
+
+    /// `Some(true)` if the criterion is met with the given confidence, `Some(false)` if it's not met with the given confidence, or `None` if more seeds are needed.
+    pub(crate) fn verdict(&self, confidence: f64) -> Option<bool> {
+        let interval = self.interval(confidence)?;
+        if interval.lower > self.threshold() {
+            Some(true)
+        } else if interval.upper <= self.threshold() {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the criterion is met according to the point estimate, ignoring confidence.
+    pub(crate) fn point_verdict(&self) -> bool {
+        match *self {
+            Self::Proportion { hits, trials, threshold } => trials > 0 && hits as f64 / trials as f64 > threshold,
+            Self::Ratio { ref samples, threshold } => {
+                let num_successes = samples.iter().map(|&(success, _)| success).sum::<f64>();
+                // no successful seeds means infinite instructions until success
+                num_successes == 0.0 || samples.iter().map(|&(_, instructions)| instructions).sum::<f64>() / num_successes > threshold
+            }
+        }
+    }
+}
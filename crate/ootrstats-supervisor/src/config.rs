@@ -4,7 +4,12 @@ use {
         sync::Arc,
     },
     bytesize::ByteSize,
-    serde::Deserialize,
+    lazy_regex::Regex,
+    serde::{
+        Deserialize,
+        Deserializer,
+        de::Error as _,
+    },
     wheel::fs,
 };
 #[cfg(windows)] use directories::ProjectDirs;
@@ -17,6 +22,45 @@ pub struct Config {
     pub(crate) log: bool,
     pub(crate) stats_dir: Option<PathBuf>,
     pub workers: Vec<Worker>,
+    #[serde(default = "default_infra_failures")]
+    pub(crate) infra_failures: Vec<InfraFailureRule>,
+}
+
+fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Regex>, D::Error> {
+    Option::<String>::deserialize(deserializer)?.map(|regex| Regex::new(&regex).map_err(D::Error::custom)).transpose()
+}
+
+/// A rule for recognizing seeds that failed because of a problem with the worker rather than with the randomizer.
+///
+/// A rule matches if all of the conditions it specifies match.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InfraFailureRule {
+    /// The signal which terminated the randomizer, e.g. 9 for `SIGKILL`.
+    signal: Option<i32>,
+    /// A regex which must match somewhere in the error log.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    stderr: Option<Regex>,
+}
+
+impl InfraFailureRule {
+    fn matches(&self, exit_signal: Option<i32>, error_log: &str) -> bool {
+        (self.signal.is_some() || self.stderr.is_some())
+        && self.signal.is_none_or(|signal| exit_signal == Some(signal))
+        && self.stderr.as_ref().is_none_or(|stderr| stderr.is_match(error_log))
+    }
+}
+
+fn default_infra_failures() -> Vec<InfraFailureRule> {
+    vec![
+        // killed by the OOM killer
+        InfraFailureRule { signal: Some(9), stderr: None },
+        InfraFailureRule { signal: None, stderr: Some(Regex::new("Cannot allocate memory").expect("invalid default regex")) },
+        // randomizer dependencies not installed on the worker
+        InfraFailureRule { signal: None, stderr: Some(Regex::new("(?m)^(?:ModuleNotFoundError|ImportError): ").expect("invalid default regex")) },
+        // missing base ROM
+        InfraFailureRule { signal: None, stderr: Some(Regex::new(r"(?m)^FileNotFoundError: .*\.[nz]64'$").expect("invalid default regex")) },
+    ]
 }
 
 fn make_5gib() -> ByteSize { ByteSize::gib(5) }
@@ -36,6 +80,8 @@ pub struct Worker {
     #[serde(default = "make_five")]
     pub(crate) min_disk_percent: f64,
     pub(crate) min_disk_mount_points: Option<Vec<PathBuf>>,
+    /// Linux only. Allocations by the randomizer fail once a seed uses more than this much memory.
+    pub(crate) memory_limit: Option<ByteSize>,
 }
 
 fn default_image_region() -> String { format!("fr-par") }
@@ -92,6 +138,12 @@ pub enum Error {
 }
 
 impl Config {
+    /// Whether a failed seed should be rolled again because it failed due to a problem with the worker.
+    pub(crate) fn is_infra_failure(&self, exit_signal: Option<i32>, error_log: &[u8]) -> bool {
+        let error_log = String::from_utf8_lossy(error_log);
+        self.infra_failures.iter().any(|rule| rule.matches(exit_signal, &error_log))
+    }
+
     pub async fn load() -> Result<Self, Error> {
         #[cfg(unix)] {
             if let Some(config_path) = BaseDirectories::new().find_config_file("ootrstats.json") {
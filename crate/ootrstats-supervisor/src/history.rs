@@ -0,0 +1,48 @@
+use {
+    std::{
+        io,
+        path::Path,
+    },
+    chrono::prelude::*,
+    serde::{
+        Deserialize,
+        Serialize,
+    },
+    wheel::fs,
+};
+
+const FILENAME: &str = "bench-history.json";
+
+/// A summary of a `bench` run, as stored in the history file in the stats root.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Entry {
+    pub(crate) github_user: String,
+    pub(crate) repo: String,
+    pub(crate) rev: String,
+    /// The label of the suite entry, or `None` if the `--suite` option wasn't used.
+    pub(crate) label: Option<String>,
+    pub(crate) date: DateTime<Utc>,
+    pub(crate) num_successes: u32,
+    pub(crate) num_failures: u32,
+    pub(crate) success_rate: f64,
+    pub(crate) average_instructions_success: u64,
+    pub(crate) average_instructions_failure: u64,
+    pub(crate) average_instructions: f64,
+    /// Average total instructions taken by the random settings script, if any.
+    pub(crate) rsl_average_instructions: Option<f64>,
+}
+
+pub(crate) async fn load(stats_root: &Path) -> wheel::Result<Vec<Entry>> {
+    match fs::read_json(stats_root.join(FILENAME)).await {
+        Ok(entries) => Ok(entries),
+        Err(wheel::Error::Io { inner, .. }) if inner.kind() == io::ErrorKind::NotFound => Ok(Vec::default()),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) async fn record(stats_root: &Path, entry: Entry) -> wheel::Result<()> {
+    let mut entries = load(stats_root).await?;
+    entries.push(entry);
+    fs::write_json(stats_root.join(FILENAME), entries).await
+}
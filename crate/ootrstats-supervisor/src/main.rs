@@ -6,6 +6,7 @@ use {
         borrow::Cow,
         collections::{
             BTreeMap,
+            BTreeSet,
             HashSet,
             hash_map::{
                 self,
@@ -21,8 +22,18 @@ use {
         },
         iter,
         num::NonZero,
-        path::PathBuf,
+        ops::{
+            Range,
+            RangeInclusive,
+        },
+        path::{
+            Path,
+            PathBuf,
+        },
+        pin::pin,
+        str::FromStr,
         sync::Arc,
+        time::Duration,
     },
     bytes::Bytes,
     chrono::prelude::*,
@@ -48,14 +59,18 @@ use {
     },
     if_chain::if_chain,
     itertools::Itertools as _,
-    lazy_regex::regex_is_match,
+    lazy_regex::{
+        Regex,
+        regex_captures,
+        regex_is_match,
+        regex_replace_all,
+    },
     nonempty_collections::{
         IntoIteratorExt as _,
         NEVec,
         NonEmptyIterator as _,
         nev,
     },
-    ootr_utils::spoiler::SpoilerLog,
     ootrstats_supervisor as _, // included directly as modules
     proc_macro2 as _, // feature config required for Span::start used in CustomExit impl
     rustls as _, // feature ring required for WebSocket connections to work
@@ -70,7 +85,10 @@ use {
         select,
         sync::mpsc,
         task::JoinError,
-        time::Instant,
+        time::{
+            Instant,
+            sleep_until,
+        },
     },
     wheel::{
         fs::{
@@ -89,52 +107,150 @@ use {
         RandoSetup,
         SeedIdx,
         Seeds,
+        Side,
+        Sweep,
         WSL,
+        WorldCounts,
         gitdir,
     },
     crate::{
+        analysis::Analysis,
         config::Config,
         msg::Message,
+        stats::Interval,
+        suite::Suite,
     },
 };
 #[cfg(windows)] use directories::ProjectDirs;
 
+mod analysis;
+mod bisect;
 mod config;
+mod history;
 mod msg;
+mod precision;
+mod query;
+mod stats;
+mod suite;
 mod worker;
 
-fn parse_traceback<'a>(worker: &Arc<str>, seed_idx: SeedIdx, error_log: &'a str) -> Result<(&'a str, &'a str), Error> {
-    let mut rev_lines = error_log.trim().lines().rev();
-    let mut msg = rev_lines.next().ok_or_else(|| Error::EmptyErrorLog(seed_idx))?;
-    let _ = rev_lines.next().ok_or_else(|| Error::MissingTraceback { worker: worker.clone(), error_log: error_log.to_owned(), missing_part: "skip" })?;
-    let mut location = rev_lines.next().ok_or_else(|| Error::MissingTraceback { worker: worker.clone(), error_log: error_log.to_owned(), missing_part: "location" })?;
-    if let Some(skip) = rev_lines.find_map(|line| if line.contains("Performance counter stats") { Some(true) } else if regex_is_match!(r"[0-9.]+ real +[0-9.]+ user +[0-9.]+ sys", line) { Some(false) } else { None }) {
-        if skip {
-            let _ = rev_lines.next().ok_or_else(|| Error::EmptyErrorLog(seed_idx));
+/// Finds the location and message of the error in a randomizer error log, used to group failures.
+///
+/// Recognizes Python tracebacks as well as panics and errors returned from `main` by the Rust CLI.
+/// For any other error log, the last line is used as both the location and the message.
+fn parse_traceback(error_log: &str) -> (&str, &str) {
+    let mut lines = error_log.lines().collect_vec();
+    // workers may append benchmark output from perf or time after the randomizer's own output
+    if let Some(pos) = lines.iter().rposition(|line| line.contains("Performance counter stats") || regex_is_match!(r"[0-9.]+ real +[0-9.]+ user +[0-9.]+ sys", line)) {
+        lines.truncate(pos);
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let Some(&last_line) = lines.last() else { return ("(empty error log)", "(empty error log)") };
+    // Python: the message is on the last line and the innermost stack frame is the last line starting with `File "`
+    if lines.iter().any(|line| line.starts_with("Traceback (most recent call last):")) {
+        if let Some(location) = lines.iter().copied().rev().find(|line| line.trim_start().starts_with("File \"")) {
+            return (location, last_line)
         }
-        msg = rev_lines.next().ok_or_else(|| Error::EmptyErrorLog(seed_idx))?;
-        let _ = rev_lines.next().ok_or_else(|| Error::MissingTraceback { worker: worker.clone(), error_log: error_log.to_owned(), missing_part: "skip (perf)" })?;
-        location = rev_lines.next().ok_or_else(|| Error::MissingTraceback { worker: worker.clone(), error_log: error_log.to_owned(), missing_part: "location (perf)" })?;
     }
-    Ok((location, msg))
+    for (idx, line) in lines.iter().copied().enumerate().rev() {
+        // Rust 1.73 and later: `thread 'main' panicked at src/main.rs:1:2:` followed by the message
+        if let Some((_, location)) = regex_captures!("^thread '.*' panicked at (.+):$", line) {
+            return (location, lines.get(idx + 1).copied().unwrap_or_default())
+        }
+        // older Rust versions: `thread 'main' panicked at 'message', src/main.rs:1:2`
+        if let Some((_, msg, location)) = regex_captures!("^thread '.*' panicked at '(.*)', (.+)$", line) {
+            return (location, msg)
+        }
+    }
+    // error returned from `main`, optionally followed by a `Caused by:` list of its sources, the innermost of which is used as the message
+    if let Some((idx, top)) = lines.iter().copied().enumerate().rev().find_map(|(idx, line)| Some((idx, line.strip_prefix("Error: ")?))) {
+        let root_cause = lines[idx + 1..].iter().copied()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && *line != "Caused by:")
+            .last()
+            .map(|line| regex_captures!("^[0-9]+: (.*)$", line).map_or(line, |(_, cause)| cause));
+        return (top, root_cause.unwrap_or(top))
+    }
+    (last_line, last_line)
+}
+
+/// The exception type of a Python error message like `ValueError: …`, or the entire message if it doesn't start with an exception type.
+fn exception_type(msg: &str) -> &str {
+    regex_captures!(r"^([A-Za-z_][A-Za-z0-9_.]*)(?::|$)", msg).map_or(msg, |(_, exception_type)| exception_type)
+}
+
+/// Applies the `failures` subcommand's normalization rules to an error message.
+fn normalize_failure_msg(msg: &str, normalize: bool, replacements: &[Replacement]) -> String {
+    let mut msg = msg.to_owned();
+    if normalize {
+        msg = regex_replace_all!(r#"'[^']*'|"[^"]*""#, &msg, |quoted: &str| format!("{0}…{0}", &quoted[..1])).into_owned();
+        msg = regex_replace_all!("[0-9]+", &msg, |_| format!("N")).into_owned();
+    }
+    for Replacement { pattern, replacement } in replacements {
+        msg = pattern.replace_all(&msg, replacement).into_owned();
+    }
+    msg
+}
+
+/// Numeric outputs of the `categorize` subcommand with more distinct values than this are displayed as a histogram even without `--histogram`.
+const HISTOGRAM_DISTINCT_VALUES: usize = 20;
+const STATUS_INTERVAL: Duration = Duration::from_millis(100);
+/// A seed is given up on after this many infrastructure failures, even if there are workers it hasn't had one on yet.
+const MAX_INFRA_FAILURES: usize = 3;
+
+/// Splits a multiworld spoiler log into one spoiler log per world.
+///
+/// Each top-level entry that's keyed by world (`"World 1"`, `"World 2"`, …) is replaced with the value for the respective world. Other entries are kept as is.
+fn split_worlds(spoiler_log: serde_json::Value) -> Vec<serde_json::Value> {
+    let world_count = spoiler_log.get("settings").and_then(|settings| settings.get("world_count")).and_then(serde_json::Value::as_u64).unwrap_or(1);
+    if world_count <= 1 { return vec![spoiler_log] }
+    (1..=world_count).map(|world| {
+        let world_key = format!("World {world}");
+        let mut world_log = spoiler_log.clone();
+        if let Some(entries) = world_log.as_object_mut() {
+            for value in entries.values_mut() {
+                if_chain! {
+                    if let Some(per_world) = value.as_object_mut();
+                    if !per_world.is_empty() && per_world.keys().all(|key| regex_is_match!("^World [0-9]+$", key));
+                    then {
+                        let world_value = per_world.remove(&world_key).unwrap_or_default();
+                        *value = world_value;
+                    }
+                }
+            }
+        }
+        world_log
+    }).collect()
 }
 
 enum ReaderMessage {
     Pending {
+        side: Side,
         seed_idx: SeedIdx,
         allowed_workers: Option<NEVec<Arc<str>>>,
     },
     Success {
+        side: Side,
         seed_idx: SeedIdx,
         worker: Arc<str>,
         instructions: Option<u64>,
         rsl_instructions: Option<u64>,
+        world_count: Option<u8>,
+        /// present if the spoiler log was replaced with the values extracted from it.
+        stored: Option<analysis::Stored>,
     },
     Failure {
+        side: Side,
         seed_idx: SeedIdx,
         worker: Arc<str>,
         instructions: Option<u64>,
         rsl_instructions: Option<u64>,
+        world_count: Option<u8>,
+        exit_signal: Option<i32>,
+        timed_out: bool,
+        out_of_memory: bool,
     },
     Done,
 }
@@ -146,6 +262,23 @@ struct Metadata {
     rsl_instructions: Option<Result<u64, String>>,
     /// always written by this version of ootrstats but may be absent in metadata from older ootrstats versions.
     worker: Arc<str>,
+    /// present if the `--world-counts` option was used. May be absent in metadata from older ootrstats versions.
+    world_count: Option<u8>,
+    /// values of the settings given to the `--sweep` option, if any.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    sweep: serde_json::Map<String, serde_json::Value>,
+    /// the signal which terminated the randomizer, if it failed that way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exit_signal: Option<i32>,
+    /// whether the randomizer was killed for exceeding the `--seed-timeout`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    timed_out: bool,
+    /// whether the randomizer failed because it exceeded the worker's `memory_limit`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    out_of_memory: bool,
+    /// the `--seed-timeout` in seconds which was in effect when the seed was rolled. May be absent in metadata from older ootrstats versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed_timeout: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -163,7 +296,9 @@ enum SeedState {
         worker: Arc<str>,
         instructions: Option<u64>,
         rsl_instructions: Option<u64>,
-        spoiler_log: serde_json::Value,
+        world_count: Option<u8>,
+        /// present if the subcommand looks at spoiler logs.
+        extracted: Option<analysis::Extracted>,
     },
     Failure {
         /// None if the seed was read from disk.
@@ -172,22 +307,359 @@ enum SeedState {
         worker: Arc<str>,
         instructions: Option<u64>,
         rsl_instructions: Option<u64>,
+        world_count: Option<u8>,
+        /// if true, the error log is the randomizer's output up to when it was killed for exceeding the `--seed-timeout`.
+        timed_out: bool,
+        /// whether the randomizer failed because it exceeded the worker's `memory_limit`.
+        out_of_memory: bool,
         error_log: Bytes,
+        /// present if the `categorize` subcommand's `--include-failures` option was used.
+        extracted: Option<analysis::Extracted>,
     },
+    /// The seed had infrastructure failures on all workers allowed to roll it, or `MAX_INFRA_FAILURES` times in total, so it's not rolled again during this run.
+    InfraFailure,
 }
 
 fn parse_json<T: DeserializeOwned>(arg: &str) -> Result<T, serde_json::Error> {
     serde_json::from_str(arg)
 }
 
+/// Parses a percentage like `2%` (or just `2`) into a fraction like `0.02`.
+fn parse_percent(arg: &str) -> Result<f64, std::num::ParseFloatError> {
+    Ok(arg.strip_suffix('%').unwrap_or(arg).trim().parse::<f64>()? / 100.0)
+}
+
+#[derive(Debug, thiserror::Error)]
+enum WorldCountsParseError {
+    #[error(transparent)] ParseInt(#[from] std::num::ParseIntError),
+    #[error("world count range {0}-{1} is empty")]
+    EmptyRange(u8, u8),
+    #[error("world counts must be at least 1")]
+    Zero,
+    #[error("world count weights must be at least 1")]
+    ZeroWeight,
+}
+
+/// The world counts and weights given to the `--world-counts` option, e.g. `2,4,8`, `2-8`, or `2:3,4:1`.
+#[derive(Clone)]
+struct WorldCountValues(Vec<(u8, u16)>);
+
+impl FromStr for WorldCountValues {
+    type Err = WorldCountsParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = Vec::default();
+        for item in s.split(',') {
+            let (range, weight) = if let Some((range, weight)) = item.split_once(':') {
+                (range, weight.trim().parse()?)
+            } else {
+                (item, 1)
+            };
+            if weight == 0 { return Err(WorldCountsParseError::ZeroWeight) }
+            let (start, end) = if let Some((start, end)) = range.split_once('-') {
+                (start.trim().parse()?, end.trim().parse()?)
+            } else {
+                let world_count = range.trim().parse()?;
+                (world_count, world_count)
+            };
+            if start == 0 { return Err(WorldCountsParseError::Zero) }
+            if start > end { return Err(WorldCountsParseError::EmptyRange(start, end)) }
+            values.extend((start..=end).map(|world_count| (world_count, weight)));
+        }
+        Ok(Self(values))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum SeedListParseError {
+    #[error(transparent)] ParseInt(#[from] std::num::ParseIntError),
+    #[error("the seed list is empty")]
+    Empty,
+    #[error("seed range {0}-{1} is empty")]
+    EmptyRange(SeedIdx, SeedIdx),
+    #[error("seed indices must be less than {}", SeedIdx::MAX)]
+    TooLarge,
+}
+
+/// The seed indices given to the `--seeds` or `--seeds-file` option, e.g. `100-199,4711`.
+///
+/// Items may also be separated by whitespace, so seed lists printed by the `failures` subcommand can be used as is.
+#[derive(Clone)]
+struct SeedList {
+    /// sorted, nonempty, and neither overlapping nor adjacent.
+    ranges: Vec<RangeInclusive<SeedIdx>>,
+    /// for each range, the number of listed seeds in the ranges before it.
+    offsets: Vec<usize>,
+}
+
+impl SeedList {
+    /// The number of listed seeds.
+    fn num_seeds(&self) -> NonZero<SeedIdx> {
+        let (last_range, last_offset) = self.ranges.last().zip(self.offsets.last()).expect("seed list should be nonempty");
+        NonZero::new((last_offset + (last_range.end() - last_range.start()) as usize + 1) as SeedIdx).expect("seed list should be nonempty")
+    }
+
+    /// The highest listed seed index.
+    fn max(&self) -> SeedIdx {
+        *self.ranges.last().expect("seed list should be nonempty").end()
+    }
+
+    /// The seed index at the given position in the list.
+    fn get(&self, pos: usize) -> SeedIdx {
+        let range_idx = self.offsets.partition_point(|&offset| offset <= pos) - 1;
+        self.ranges[range_idx].start() + (pos - self.offsets[range_idx]) as SeedIdx
+    }
+
+    /// The position of the given seed index in the list, if it's listed.
+    fn position(&self, seed_idx: SeedIdx) -> Option<usize> {
+        let range_idx = self.ranges.partition_point(|range| *range.end() < seed_idx);
+        let range = self.ranges.get(range_idx)?;
+        range.contains(&seed_idx).then(|| self.offsets[range_idx] + (seed_idx - range.start()) as usize)
+    }
+}
+
+impl FromStr for SeedList {
+    type Err = SeedListParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::default();
+        for item in s.split(|c: char| c == ',' || c.is_whitespace()).filter(|item| !item.is_empty()) {
+            let (start, end) = if let Some((start, end)) = item.split_once('-') {
+                (start.parse()?, end.parse()?)
+            } else {
+                let seed_idx = item.parse()?;
+                (seed_idx, seed_idx)
+            };
+            if start > end { return Err(SeedListParseError::EmptyRange(start, end)) }
+            if end == SeedIdx::MAX { return Err(SeedListParseError::TooLarge) }
+            items.push(start..=end);
+        }
+        items.sort_unstable_by_key(|range| *range.start());
+        // merge overlapping and adjacent ranges so each seed is listed once
+        let mut ranges = Vec::<RangeInclusive<SeedIdx>>::default();
+        for item in items {
+            match ranges.last_mut() {
+                Some(last) if *item.start() <= last.end() + 1 => if item.end() > last.end() {
+                    *last = *last.start()..=*item.end();
+                },
+                _ => ranges.push(item),
+            }
+        }
+        if ranges.is_empty() { return Err(SeedListParseError::Empty) }
+        let offsets = ranges.iter().scan(0, |offset, range| {
+            let range_offset = *offset;
+            *offset += (range.end() - range.start()) as usize + 1;
+            Some(range_offset)
+        }).collect();
+        Ok(Self { ranges, offsets })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum SweepParseError {
+    #[error(transparent)] ParseInt(#[from] std::num::ParseIntError),
+    #[error("sweep range {0} is empty")]
+    EmptyRange(String),
+    #[error("sweeps must be given as name=values")]
+    MissingValues,
+}
+
+/// Splits a list of JSON values at the commas which aren't inside of an array, object, or string.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut items = Vec::default();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut item_start = 0;
+    for (idx, c) in s.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else {
+            match c {
+                '"' => in_string = true,
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    items.push(&s[item_start..idx]);
+                    item_start = idx + 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    items.push(&s[item_start..]);
+    items
+}
+
+/// Parses an argument to the `--sweep` option, e.g. `logic_rules=glitchless,none` or `triforce_goal_per_world=1..=100`.
+///
+/// Each comma-separated value is either an integer range (`start..end` or `start..=end`) or a JSON value. Values that aren't valid JSON are taken as strings. Commas inside of JSON arrays, objects, and strings don't separate values.
+fn parse_sweep(arg: &str) -> Result<Sweep, SweepParseError> {
+    let (setting, values_arg) = arg.split_once('=').ok_or(SweepParseError::MissingValues)?;
+    if setting.trim().is_empty() { return Err(SweepParseError::MissingValues) }
+    let mut values = Vec::default();
+    for item in split_top_level(values_arg) {
+        let item = item.trim();
+        if let Some((_, start, inclusive, end)) = regex_captures!("^(-?[0-9]+) *\\.\\.(=?) *(-?[0-9]+)$", item) {
+            let start = start.parse::<i64>()?;
+            let end = if inclusive.is_empty() {
+                end.parse::<i64>()? - 1
+            } else {
+                end.parse::<i64>()?
+            };
+            if start > end { return Err(SweepParseError::EmptyRange(item.to_owned())) }
+            values.extend((start..=end).map(serde_json::Value::from));
+        } else {
+            values.push(serde_json::from_str(item).unwrap_or_else(|_| serde_json::Value::String(item.to_owned())));
+        }
+    }
+    Ok(Sweep { setting: setting.trim().to_owned(), values })
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ReplacementParseError {
+    #[error(transparent)] Regex(#[from] lazy_regex::regex::Error),
+    #[error("replacements must be given as pattern=replacement")]
+    MissingReplacement,
+}
+
+/// A regex replacement applied to error messages by the `failures` subcommand.
+#[derive(Clone)]
+struct Replacement {
+    pattern: Regex,
+    /// may refer to capture groups of the pattern, e.g. `$1`.
+    replacement: String,
+}
+
+/// Parses an argument to the `failures` subcommand's `--replace` option, e.g. `after [0-9]+ tries=after N tries`.
+///
+/// The pattern ends at the first `=`.
+fn parse_replacement(arg: &str) -> Result<Replacement, ReplacementParseError> {
+    let (pattern, replacement) = arg.split_once('=').ok_or(ReplacementParseError::MissingReplacement)?;
+    Ok(Replacement { pattern: Regex::new(pattern)?, replacement: replacement.to_owned() })
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DurationParseError {
+    #[error(transparent)] ParseInt(#[from] std::num::ParseIntError),
+    #[error("durations must be given as a number followed by a unit (s, m, or h), e.g. 30m")]
+    Syntax,
+    #[error("durations must be positive")]
+    Zero,
+}
+
+/// Parses an argument to the `--seed-timeout` option, e.g. `30m`, `90s`, or `2h`.
+fn parse_duration(arg: &str) -> Result<Duration, DurationParseError> {
+    let (_, amount, unit) = regex_captures!("^([0-9]+) *(s|m|h)$", arg.trim()).ok_or(DurationParseError::Syntax)?;
+    let amount = amount.parse::<u64>()?;
+    if amount == 0 { return Err(DurationParseError::Zero) }
+    Ok(Duration::from_secs(amount.saturating_mul(match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        _ => unreachable!(),
+    })))
+}
+
+/// Splits the seed states of one revision into one group per sweep point, or a single group if the `--sweep` option wasn't used.
+fn group_by_sweep_point(sweeps: &[Sweep], seeds: Option<&SeedList>, seed_states: Vec<SeedState>) -> Vec<(Option<serde_json::Map<String, serde_json::Value>>, Vec<(SeedIdx, SeedState)>)> {
+    let mut groups = (0..ootrstats::num_sweep_points(sweeps))
+        .map(|point_idx| ((!sweeps.is_empty()).then(|| ootrstats::sweep_point(sweeps, point_idx)), Vec::default()))
+        .collect_vec();
+    for (pos, state) in seed_states.into_iter().enumerate() {
+        let seed_idx = nth_seed(seeds, pos);
+        groups[ootrstats::sweep_point_idx(sweeps, seed_idx)].1.push((seed_idx, state));
+    }
+    groups
+}
+
+/// The index of the seed at the given position among the seeds being rolled. Without `--seeds`, these are the seeds `0..num_seeds`.
+fn nth_seed(seeds: Option<&SeedList>, pos: usize) -> SeedIdx {
+    if let Some(seeds) = seeds {
+        seeds.get(pos)
+    } else {
+        pos as SeedIdx
+    }
+}
+
+/// The inverse of [`nth_seed`].
+fn seed_pos(seeds: Option<&SeedList>, seed_idx: SeedIdx) -> usize {
+    if let Some(seeds) = seeds {
+        seeds.position(seed_idx).expect("got a seed which isn't in the --seeds list")
+    } else {
+        seed_idx as usize
+    }
+}
+
+/// The position of the given seed in the list of seed states. With `--ab-rev`, the seeds of both revisions are interleaved so that workers alternate between the revisions as they pick up pending seeds.
+fn seed_slot(ab: bool, seeds: Option<&SeedList>, side: Side, seed_idx: SeedIdx) -> usize {
+    let pos = seed_pos(seeds, seed_idx);
+    if ab {
+        2 * pos + match side {
+            Side::A => 0,
+            Side::B => 1,
+        }
+    } else {
+        pos
+    }
+}
+
+/// The revision whose seed is in the given position in the list of seed states.
+fn slot_side(ab: bool, slot: usize) -> Side {
+    if ab && slot % 2 == 1 { Side::B } else { Side::A }
+}
+
+/// The inverse of [`seed_slot`].
+fn slot_seed(ab: bool, seeds: Option<&SeedList>, slot: usize) -> (Side, SeedIdx) {
+    (slot_side(ab, slot), nth_seed(seeds, if ab { slot / 2 } else { slot }))
+}
+
+/// The number of seeds from the given revision of an A/B run (or from the only revision otherwise) whose state matches the predicate.
+fn count_seeds(ab: bool, seed_states: &[SeedState], side: Side, pred: impl Fn(&SeedState) -> bool) -> usize {
+    seed_states.iter().enumerate().filter(|&(slot, state)| slot_side(ab, slot) == side && pred(state)).count()
+}
+
+fn is_allowed(allowed_workers: &HashMap<usize, NEVec<Arc<str>>>, slot: usize, worker: &Arc<str>) -> bool {
+    allowed_workers.get(&slot).is_none_or(|allowed_workers| allowed_workers.contains(worker))
+}
+
+/// Whether the given worker may be assigned the seed in the given slot.
+///
+/// Workers on which the seed had an infrastructure failure are avoided.
+fn may_roll(allowed_workers: &HashMap<usize, NEVec<Arc<str>>>, infra_failures: &[Vec<Arc<str>>], slot: usize, worker: &Arc<str>) -> bool {
+    is_allowed(allowed_workers, slot, worker) && !infra_failures[slot].contains(worker)
+}
+
+/// The state of the seed in the given slot after an infrastructure failure: rolled again unless it had one on all workers that are allowed to roll it or too many in total.
+fn after_infra_failure(allowed_workers: &HashMap<usize, NEVec<Arc<str>>>, infra_failures: &[Vec<Arc<str>>], worker_names: &[Arc<str>], slot: usize) -> SeedState {
+    if infra_failures[slot].len() >= MAX_INFRA_FAILURES || worker_names.iter().filter(|worker| is_allowed(allowed_workers, slot, worker)).all(|worker| infra_failures[slot].contains(worker)) {
+        SeedState::InfraFailure
+    } else {
+        SeedState::Pending
+    }
+}
+
+fn stats_seed_dir(stats_dir: &Path, ab_stats_dir: Option<&Path>, side: Side, seed_idx: SeedIdx) -> PathBuf {
+    match side {
+        Side::A => stats_dir,
+        Side::B => ab_stats_dir.expect("got a seed from the second revision of an A/B run without --ab-rev"),
+    }.join(seed_idx.to_string())
+}
+
 #[derive(Clone, clap::Parser)]
 #[clap(version, bin_name("ootrstats"))]
 struct Args {
     // randomizer settings
 
-    /// Run the benchmarking suite.
-    #[clap(long, conflicts_with("rsl"), conflicts_with("preset"), conflicts_with("settings"))]
-    suite: bool,
+    /// Run a benchmarking suite, given as the name or path of a suite file. Defaults to the built-in suite.
+    #[clap(long, num_args(0..=1), require_equals(true), default_missing_value("default"), conflicts_with("rsl"), conflicts_with("preset"), conflicts_with("settings"), conflicts_with("draft"))]
+    suite: Option<String>,
     /// Use the random settings script to determine settings.
     #[clap(long)]
     rsl: bool,
@@ -199,6 +671,9 @@ struct Args {
     branch: Option<String>,
     #[clap(long)]
     rev: Option<gix::ObjectId>,
+    /// Also roll seeds on this git revision, alternating with the main revision, and report the results for both revisions.
+    #[clap(long, conflicts_with("world_counts"))]
+    ab_rev: Option<gix::ObjectId>,
     #[clap(short, long)]
     preset: Option<String>,
     /// Settings string for the randomizer.
@@ -216,30 +691,54 @@ struct Args {
     /// Specifies a JSON object of a plandomizer file on the command line.
     #[clap(long, default_value = "{}", conflicts_with("rsl"), value_parser = parse_json::<serde_json::Map<String, serde_json::Value>>)]
     plando: serde_json::Map<String, serde_json::Value>,
-    /// Generate seeds with varying world counts.
-    #[clap(long, conflicts_with("rsl"))]
-    world_counts: bool,
+    /// Generate seeds with varying world counts, e.g. `2,4,8`, `2-8`, or `2:3,4:1` (world count 2 with weight 3 and world count 4 with weight 1).
+    #[clap(long, conflicts_with("rsl"), num_args(0..=1), require_equals(true), default_missing_value("1-255"))]
+    world_counts: Option<WorldCountValues>,
+    /// How many seeds to roll for each world count given to --world-counts, multiplied by the weight.
+    #[clap(long, requires("world_counts"), default_value = "1")]
+    seeds_per_world_count: NonZero<u16>,
+    /// Vary a setting between seeds, e.g. `logic_rules=glitchless,none` or `triforce_goal_per_world=1..=100`. Can be repeated to sweep all combinations of values.
+    #[clap(long = "sweep", value_parser = parse_sweep, conflicts_with("rsl"), conflicts_with("world_counts"))]
+    sweeps: Vec<Sweep>,
     /// Generates a fixed seed. Useful for confirming suspected unseeded randomization.
     #[clap(long)]
     seed: Option<String>,
     /// Generate .zpf/.zpfz patch files.
     #[clap(long)]
     patch: bool,
+    /// Delete each seed's spoiler log and patch file after extracting the values needed by the subcommand.
+    #[clap(long)]
+    discard_logs: bool,
 
     // ootrstats settings
 
     /// Specifies the ootrstats configuration as a JSON object on the command line that will be used instead of reading the configuration from a file.
     #[clap(long, value_parser = parse_json::<Config>)]
     config: Option<Config>,
-    /// Sample size — how many seeds to roll.
-    #[clap(short, long, default_value = "16384", default_value_if("world_counts", "true", Some("255")))]
-    num_seeds: NonZero<SeedIdx>,
+    /// Sample size — how many seeds to roll. Defaults to 16384, or to all seeds in the distribution if --world-counts is used. With the find subcommand, this is the maximum number of seeds to try and defaults to 65535.
+    #[clap(short, long)]
+    num_seeds: Option<NonZero<SeedIdx>>,
+    /// Keep rolling seeds until this many of them succeeded, instead of rolling a fixed number of seeds. Failures are still counted towards the failure rate.
+    #[clap(long, conflicts_with("num_seeds"), conflicts_with("until_ci"))]
+    num_successes: Option<NonZero<SeedIdx>>,
+    /// Only roll and analyze the seeds with these indices, e.g. `100-199,4711`.
+    #[clap(long, conflicts_with("num_seeds"), conflicts_with("num_successes"), conflicts_with("until_ci"))]
+    seeds: Option<SeedList>,
+    /// Like --seeds, but reads the list of seed indices from this file. Items may also be separated by whitespace or newlines.
+    #[clap(long, conflicts_with("seeds"), conflicts_with("num_seeds"), conflicts_with("num_successes"), conflicts_with("until_ci"))]
+    seeds_file: Option<PathBuf>,
+    /// Stop rolling new seeds once the 95% confidence interval of the failure rate (or of the frequencies of the categorize subcommand's query outputs) is within ± this value of the estimate, e.g. `0.5%`.
+    #[clap(long, value_parser = parse_percent, conflicts_with("sweeps"))]
+    until_ci: Option<f64>,
     /// If there are more available cores than remaining seeds, roll the same seed multiple times and keep the one that finishes first.
     #[clap(long)]
     race: bool,
     /// If the randomizer errors, retry instead of recording the failure.
     #[clap(long)]
     retry_failures: bool,
+    /// Kill the randomizer and record the seed as timed out if generating it takes longer than this, e.g. `30m`, `90s`, or `2h`.
+    #[clap(long, value_parser = parse_duration)]
+    seed_timeout: Option<Duration>,
     /// Delete any existing stats instead of reusing them.
     #[clap(long)]
     clean: bool,
@@ -255,6 +754,12 @@ struct Args {
     /// Randomizer or RSL script git revision to compare against when benchmarking.
     #[clap(long)]
     baseline_rev: Option<gix::ObjectId>,
+    /// With the bench subcommand, fail if the average instruction count is significantly higher than at --baseline-rev by more than this much, e.g. `2%`.
+    #[clap(long, requires("baseline_rev"), value_parser = parse_percent)]
+    max_regression: Option<f64>,
+    /// With the bench subcommand, fail if the failure rate is significantly higher than at --baseline-rev by more than this many percentage points, e.g. `1%`.
+    #[clap(long, requires("baseline_rev"), value_parser = parse_percent)]
+    max_failure_rate: Option<f64>,
     #[clap(subcommand)]
     subcommand: Option<Subcommand>,
 }
@@ -268,16 +773,113 @@ enum Subcommand {
         #[clap(long)]
         uncompressed: bool,
     },
-    /// Categorize spoiler logs using a JSON query.
+    /// Categorize spoiler logs using one or more JSON queries.
     Categorize {
-        query: String,
+        #[clap(required_unless_present = "query_files")]
+        query: Option<String>,
+        /// Also run the named queries from this TOML or JSON file. Can be given multiple times.
+        #[clap(long = "query-file")]
+        query_files: Vec<PathBuf>,
+        /// Cross-tabulate the outputs of each query against the outputs of this JSON query.
+        #[clap(long)]
+        by: Option<String>,
+        /// Only count seeds for which this JSON query outputs a value other than `false` or `null`.
+        #[clap(long = "where")]
+        filter: Option<String>,
+        /// Run the queries separately for each world of a multiworld seed.
+        #[clap(long)]
+        per_world: bool,
+        /// Also run the queries on failed seeds, with the error log and parsed traceback as input.
+        #[clap(long)]
+        include_failures: bool,
+        /// Display numeric outputs as histograms even if there are only a few distinct values.
+        #[clap(long)]
+        histogram: bool,
+        /// The maximum number of bins in histograms.
+        #[clap(long, default_value = "10")]
+        bins: NonZero<usize>,
     },
     /// Display most common exceptions thrown by the randomizer.
-    Failures,
+    Failures {
+        /// How many groups of failures to display.
+        #[clap(long, default_value = "10")]
+        top: usize,
+        /// Group failures by exception type instead of by the location where the exception was raised.
+        #[clap(long)]
+        by_exception_type: bool,
+        /// Replace numbers and quoted names in error messages with placeholders, so that messages which only differ in these are counted as the same.
+        #[clap(short, long)]
+        normalize: bool,
+        /// Apply a regex replacement, given as pattern=replacement, to error messages. Can be given multiple times.
+        #[clap(long = "replace", value_parser = parse_replacement)]
+        replacements: Vec<Replacement>,
+        /// List the indices of all seeds in each group.
+        #[clap(long)]
+        list_seeds: bool,
+    },
+    /// Roll seeds until the given number of them match a JSON query. Spoiler logs and patches of seeds that don't match are deleted.
+    Find {
+        /// Seeds match if this JSON query outputs a value other than `false` or `null`.
+        query: String,
+        /// How many matching seeds to find.
+        #[clap(short, long, default_value = "1")]
+        count: NonZero<usize>,
+    },
     /// Count chest appearances in Mido's house for the midos.house favicon.
     MidosHouse {
         out_path: PathBuf,
     },
+    /// Find the first revision between --good and --bad which meets the given criterion.
+    #[clap(group(clap::ArgGroup::new("criterion").required(true).args(["failure_rate", "query", "instructions"])))]
+    Bisect {
+        /// A revision which doesn't meet the criterion.
+        #[clap(long)]
+        good: gix::ObjectId,
+        /// A descendant of --good which meets the criterion.
+        #[clap(long)]
+        bad: gix::ObjectId,
+        /// Criterion: more than this many seeds fail, e.g. `5%`.
+        #[clap(long, value_parser = parse_percent)]
+        failure_rate: Option<f64>,
+        /// Criterion: this JSON query outputs a value other than `false` or `null` for more than --frequency of successful seeds.
+        #[clap(long, requires("frequency"))]
+        query: Option<String>,
+        #[clap(long, requires("query"), value_parser = parse_percent)]
+        frequency: Option<f64>,
+        /// Criterion: the average total CPU instructions until success are above this number.
+        #[clap(long)]
+        instructions: Option<f64>,
+        /// How confident to be about each revision before moving on.
+        #[clap(long, default_value = "95%", value_parser = parse_percent)]
+        confidence: f64,
+        /// How many seeds to roll for each revision at first. This is doubled until the result is significant.
+        #[clap(long, default_value = "128")]
+        min_seeds: NonZero<SeedIdx>,
+        /// Decide based on the point estimate if the result isn't significant with this many seeds.
+        #[clap(long, default_value = "16384")]
+        max_seeds: NonZero<SeedIdx>,
+    },
+    /// Roll a single seed again on a local worker, keeping all of the randomizer's output along with a script that runs the exact same command.
+    Repro {
+        seed_idx: SeedIdx,
+        /// The directory to write the outputs to. Defaults to `repro-<seed_idx>` in the current directory.
+        #[clap(short, long)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Show benchmark results recorded by previous runs of the bench subcommand across revisions.
+    BenchHistory {
+        /// Flag revisions whose average instruction count increased by more than this much compared to the previous revision.
+        #[clap(long, default_value = "2%", value_parser = parse_percent)]
+        threshold: f64,
+    },
+    /// Show how failure rate and generation cost scale with the world count. Requires --world-counts.
+    WorldCounts {
+        /// Measure CPU instructions like the bench subcommand.
+        #[clap(long)]
+        bench: bool,
+        #[clap(long, requires("bench"))]
+        uncompressed: bool,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -292,13 +894,28 @@ enum Error {
     #[error(transparent)] GitHeadId(#[from] gix::reference::head_id::Error),
     #[error(transparent)] GitOpen(#[from] gix::open::Error),
     #[error(transparent)] GitPrepareFetch(#[from] gix::remote::fetch::prepare::Error),
+    #[error(transparent)] GitRevWalk(#[from] gix::revision::walk::Error),
+    #[error(transparent)] GitRevWalkIter(#[from] gix::traverse::commit::simple::Error),
     #[error(transparent)] GitValidateRefName(#[from] gix::validate::reference::name::Error),
+    #[error(transparent)] JaqToJson(#[from] query::ToJsonError),
     #[error(transparent)] Json(#[from] serde_json::Error),
     #[error(transparent)] Task(#[from] JoinError),
+    #[error(transparent)] Toml(#[from] toml::de::Error),
     #[error(transparent)] TryFromInt(#[from] std::num::TryFromIntError),
     #[error(transparent)] ReaderSend(#[from] mpsc::error::SendError<ReaderMessage>),
+    #[error(transparent)] SeedList(#[from] SeedListParseError),
+    #[error(transparent)] Suite(#[from] suite::Error),
     #[error(transparent)] Utf8(#[from] std::str::Utf8Error),
     #[error(transparent)] Wheel(#[from] wheel::Error),
+    #[error("benchmark gate failed")]
+    BenchGate(Vec<BenchVerdict>),
+    #[error("the {0} subcommand can't be used with --ab-rev")]
+    AbRevSubcommand(&'static str),
+    #[error("--good {good} is not an ancestor of --bad {bad}")]
+    BisectNotAncestor {
+        good: gix::ObjectId,
+        bad: gix::ObjectId,
+    },
     #[error("cancelled by user")]
     Cancelled,
     #[error("error parsing draft spec: {source}")]
@@ -306,8 +923,6 @@ enum Error {
         file: String,
         source: syn::Error,
     },
-    #[error("empty error log")]
-    EmptyErrorLog(SeedIdx),
     #[error("failed to compile JSON query")]
     JaqCompile,
     #[error("failed to load JSON query")]
@@ -317,18 +932,20 @@ enum Error {
     #[cfg(windows)]
     #[error("user folder not found")]
     MissingHomeDir,
-    #[error("missing traceback from error log")]
-    MissingTraceback {
-        worker: Arc<str>,
-        error_log: String,
-        missing_part: &'static str,
-    },
+    #[error("no benchmarked seeds found for the baseline revision, run the bench subcommand with --rev set to the baseline revision first")]
+    NoBaselineBench,
     #[error("no default remote configured for randomizer repo")]
     NoDefaultRemote,
+    #[error("the repro subcommand requires a local worker")]
+    NoLocalWorker,
+    #[error("the world-counts subcommand requires the --world-counts option")]
+    NoWorldCounts,
+    #[error("the {0} subcommand can't be used with --seeds or --seeds-file")]
+    SeedsSubcommand(&'static str),
     #[error("found both spoiler and error logs for a seed")]
     SuccessAndFailure,
-    #[error("at most 255 seeds may be generated with the --world-counts option")]
-    TooManyWorlds,
+    #[error("at most {0} seeds may be generated with the given --world-counts")]
+    TooManyWorlds(usize),
     #[error("error(s) in worker(s): {}", .worker_errors.iter().map(|(worker, source)| format!("{worker}: {source}")).format(", "))]
     Worker {
         worker_errors: Vec<(Arc<str>, worker::Error)>,
@@ -351,22 +968,33 @@ impl IsNetworkError for Error {
             | Self::GitHeadId(_)
             | Self::GitOpen(_)
             | Self::GitPrepareFetch(_)
+            | Self::GitRevWalk(_)
+            | Self::GitRevWalkIter(_)
             | Self::GitValidateRefName(_)
+            | Self::JaqToJson(_)
             | Self::Json(_)
             | Self::Task(_)
+            | Self::Toml(_)
             | Self::TryFromInt(_)
             | Self::ReaderSend(_)
+            | Self::SeedList(_)
+            | Self::Suite(_)
             | Self::Utf8(_)
+            | Self::AbRevSubcommand(_)
+            | Self::BenchGate(_)
+            | Self::BisectNotAncestor { .. }
             | Self::Cancelled
             | Self::DraftParse { .. }
-            | Self::EmptyErrorLog(_)
             | Self::JaqCompile
             | Self::JaqLoad
             | Self::JaqRun
-            | Self::MissingTraceback { .. }
+            | Self::NoBaselineBench
             | Self::NoDefaultRemote
+            | Self::NoLocalWorker
+            | Self::NoWorldCounts
+            | Self::SeedsSubcommand(_)
             | Self::SuccessAndFailure
-            | Self::TooManyWorlds
+            | Self::TooManyWorlds(_)
             | Self::WorkerNotFound
                 => false,
             #[cfg(windows)] Self::MissingHomeDir => false,
@@ -392,6 +1020,10 @@ impl wheel::CustomExit for Error {
         }
         eprintln!("\r");
         match self {
+            Self::BenchGate(_) => {
+                eprintln!("{cmd_name}: {self}\r");
+                std::process::exit(2)
+            }
             Self::Cancelled => eprintln!("cancelled by pressing C or D\r"),
             Self::DraftParse { file: _ /*TODO display the span of code? */, source } => {
                 eprintln!("{cmd_name}: error parsing draft spec: {source}\r");
@@ -446,9 +1078,248 @@ impl wheel::CustomExit for Error {
     }
 }
 
-async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error> {
-    if args.world_counts && args.num_seeds.get() > 255 {
-        return Err(Error::TooManyWorlds)
+/// The confidence level at which the `--max-regression` and `--max-failure-rate` options consider a difference to the baseline significant.
+const BENCH_GATE_CONFIDENCE: f64 = 0.95;
+
+/// The result of checking a benchmark against the `--max-regression` and `--max-failure-rate` options.
+#[derive(Debug, Serialize)]
+pub(crate) struct BenchVerdict {
+    pub(crate) passed: bool,
+    pub(crate) label: Option<String>,
+    pub(crate) rev: String,
+    /// present if the `--sweep` option was used.
+    pub(crate) settings: Option<serde_json::Map<String, serde_json::Value>>,
+    pub(crate) baseline_rev: Option<String>,
+    pub(crate) confidence: f64,
+    pub(crate) average_instructions: Option<Interval>,
+    pub(crate) baseline_average_instructions: Option<Interval>,
+    /// relative change in average instructions compared to the baseline.
+    pub(crate) regression: Option<Interval>,
+    pub(crate) max_regression: Option<f64>,
+    pub(crate) failure_rate: Option<Interval>,
+    pub(crate) baseline_failure_rate: Option<Interval>,
+    /// difference between the failure rate and the baseline failure rate.
+    pub(crate) failure_rate_increase: Option<Interval>,
+    pub(crate) max_failure_rate: Option<f64>,
+}
+
+/// Reads `(1 or 0 for success or failure, instructions)` for each benchmarked seed with one of the given indices in the given stats directory.
+async fn read_bench_samples(stats_dir: &Path, seed_idxs: &[SeedIdx]) -> Result<Vec<(f64, f64)>, Error> {
+    let mut samples = Vec::default();
+    for &seed_idx in seed_idxs {
+        let seed_path = stats_dir.join(seed_idx.to_string());
+        let success = fs::exists(seed_path.join("spoiler.json")).await?;
+        if !success && !fs::exists(seed_path.join("error.log")).await? { continue }
+        let Metadata { instructions: Some(Ok(instructions)), .. } = fs::read_json(seed_path.join("metadata.json")).await? else { continue };
+        samples.push((if success { 1.0 } else { 0.0 }, instructions as f64));
+    }
+    Ok(samples)
+}
+
+/// The worker which rolled the seed with the given index in the baseline stats directory, if any, so that it can be benchmarked on the same worker.
+async fn baseline_worker(baseline_stats_dir: Option<&Path>, seed_idx: SeedIdx) -> Result<Option<NEVec<Arc<str>>>, Error> {
+    let Some(baseline_stats_dir) = baseline_stats_dir else { return Ok(None) };
+    match fs::read_json(baseline_stats_dir.join(seed_idx.to_string()).join("metadata.json")).await {
+        Ok(Metadata { worker, .. }) => Ok(Some(nev![worker])),
+        Err(wheel::Error::Io { inner, .. }) if inner.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Updates the local shallow clone of the given repository, creating it if it doesn't exist yet.
+async fn update_repo(github_user: &str, repo: &str, branch: Option<&str>) -> Result<(PathBuf, gix::Repository), Error> {
+    let mut dir_parent = gitdir().await?.join("github.com").join(github_user).join(repo);
+    let dir_name = if let Some(branch) = branch {
+        dir_parent = dir_parent.join("branch");
+        branch
+    } else {
+        "main"
+    };
+    let dir = dir_parent.join(dir_name);
+    let repo = if fs::exists(&dir).await? {
+        let repo = gix::open(&dir)?;
+        repo.find_default_remote(gix::remote::Direction::Fetch).ok_or(Error::NoDefaultRemote)??
+            .connect(gix::remote::Direction::Fetch)?
+            .prepare_fetch(gix::progress::Discard /*TODO show progress on command line? */, Default::default())?
+            .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(NonZero::<u32>::MIN))
+            .receive(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?;
+        Command::new("git").arg("reset").arg("--hard").arg(format!("origin/{}", branch.unwrap_or("HEAD"))).current_dir(&dir).check("git reset").await?; //TODO use gix, blocked on https://github.com/GitoxideLabs/gitoxide/issues/301
+        repo
+    } else {
+        fs::create_dir_all(&dir_parent).await?;
+        let mut clone = gix::prepare_clone(format!("https://github.com/{github_user}/{repo}.git"), &*dir)?
+            .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(NonZero::<u32>::MIN));
+        if let Some(branch) = branch {
+            clone = clone.with_ref_name(Some(branch))?;
+        }
+        clone
+            .fetch_then_checkout(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?.0
+            .main_worktree(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?
+            .0
+    };
+    Ok((dir, repo))
+}
+
+fn stats_root(config: &mut Config) -> Result<PathBuf, Error> {
+    Ok(if let Some(stats_dir) = config.stats_dir.take() {
+        stats_dir
+    } else {
+        #[cfg(windows)] let project_dirs = ProjectDirs::from("net", "Fenhl", "ootrstats").ok_or(Error::MissingHomeDir)?;
+        #[cfg(windows)] { project_dirs.data_dir().to_owned() }
+        #[cfg(unix)] { ootrstats::cache_dir().at_unknown()? }
+    })
+}
+
+async fn bench_history(args: Args, threshold: f64) -> Result<(), Error> {
+    let mut config = if let Some(config) = args.config {
+        config
+    } else {
+        Config::load().await?
+    };
+    let mut stdout = stdout();
+    let mut groups = BTreeMap::<_, HashMap<_, history::Entry>>::default();
+    for entry in history::load(&stats_root(&mut config)?).await? {
+        // only keep the most recent run of each revision
+        match groups.entry((entry.github_user.clone(), entry.repo.clone(), entry.label.clone())).or_default().entry(entry.rev.clone()) {
+            hash_map::Entry::Occupied(mut prev) => if entry.date > prev.get().date {
+                prev.insert(entry);
+            },
+            hash_map::Entry::Vacant(slot) => { slot.insert(entry); }
+        }
+    }
+    for ((github_user, repo, label), revs) in groups {
+        Message::BenchHistoryGroup { github_user: &github_user, repo: &repo, label: label.as_deref() }.print(args.json_messages, &mut stdout)?;
+        let mut prev_average_instructions = None;
+        for entry in revs.into_values().sorted_unstable_by_key(|entry| entry.date) {
+            let change = prev_average_instructions.map(|prev_average_instructions| entry.average_instructions / prev_average_instructions - 1.0);
+            prev_average_instructions = Some(entry.average_instructions);
+            Message::BenchHistoryEntry {
+                regression: change.is_some_and(|change| change > threshold),
+                entry, change,
+            }.print(args.json_messages, &mut stdout)?;
+        }
+    }
+    Ok(())
+}
+
+/// The result of a call to [`cli`].
+struct Outcome {
+    cancelled: bool,
+    /// present if the bisect subcommand was used.
+    bisect_sample: Option<bisect::Sample>,
+}
+
+async fn bisect(args: Args) -> Result<bool, Error> {
+    let Some(Subcommand::Bisect { good, bad, confidence, min_seeds, max_seeds, .. }) = args.subcommand else { unreachable!() };
+    let repo_name = args.repo.clone().unwrap_or_else(|| if args.rsl { format!("plando-random-settings") } else { format!("OoT-Randomizer") });
+    let (dir, mut repo) = update_repo(&args.github_user, &repo_name, args.branch.as_deref()).await?;
+    if repo.is_shallow() {
+        Command::new("git").arg("fetch").arg("--unshallow").current_dir(&dir).check("git fetch").await?; //TODO use gix
+        repo = gix::open(&dir)?;
+    }
+    // otherwise, nothing would be hidden from the walk below and the entire history would be bisected
+    if !Command::new("git").arg("merge-base").arg("--is-ancestor").arg(good.to_string()).arg(bad.to_string()).current_dir(&dir).status().await.at_command("git merge-base")?.success() { //TODO use gix
+        return Err(Error::BisectNotAncestor { good, bad })
+    }
+    // newest first, i.e. commits[0] is the known bad commit and the known good commit is just past the end
+    let commits = repo.rev_walk([bad])
+        .with_hidden([good])
+        .first_parent_only()
+        .all()?
+        .map(|info| Ok::<_, Error>(info?.id))
+        .try_collect::<_, Vec<_>, _>()?;
+    let mut stdout = stdout();
+    let mut bad_idx = 0;
+    let mut good_idx = commits.len();
+    let mut significant = true;
+    while good_idx - bad_idx > 1 {
+        let mid = (bad_idx + good_idx) / 2;
+        let rev = commits[mid];
+        let mut num_seeds = min_seeds.min(max_seeds);
+        let is_bad = loop {
+            let Outcome { cancelled, bisect_sample } = cli(Some(&format!("{rev} ({} revisions left)", good_idx - bad_idx - 1)), Args {
+                rev: Some(rev),
+                branch: None,
+                suite: None,
+                num_seeds: Some(num_seeds),
+                num_successes: None,
+                ..args.clone()
+            }).await?;
+            if cancelled { return Ok(true) }
+            let sample = bisect_sample.expect("bisect subcommand should produce a sample");
+            let verdict = sample.verdict(confidence);
+            Message::BisectStep {
+                rev: rev.to_string(),
+                num_seeds: num_seeds.get(),
+                interval: sample.interval(confidence),
+                proportion: sample.is_proportion(),
+                threshold: sample.threshold(),
+                verdict,
+            }.print(args.json_messages, &mut stdout)?;
+            match verdict {
+                Some(is_bad) => break is_bad,
+                None if num_seeds == max_seeds => {
+                    significant = false;
+                    break sample.point_verdict()
+                }
+                None => num_seeds = num_seeds.saturating_mul(NonZero::<SeedIdx>::new(2).unwrap()).min(max_seeds),
+            }
+        };
+        if is_bad {
+            bad_idx = mid;
+        } else {
+            good_idx = mid;
+        }
+    }
+    Message::BisectResult { rev: commits.get(bad_idx).copied().unwrap_or(bad).to_string(), significant }.print(args.json_messages, &mut stdout)?;
+    Ok(false)
+}
+
+async fn cli(label: Option<&str>, mut args: Args) -> Result<Outcome, Error> {
+    let world_counts = args.world_counts.take().map(|WorldCountValues(values)| WorldCounts { values, seeds_per_value: args.seeds_per_world_count.get() });
+    let seeds = if let Some(ref seeds_file) = args.seeds_file {
+        Some(fs::read_to_string(seeds_file).await?.parse::<SeedList>()?)
+    } else {
+        args.seeds.take()
+    };
+    if seeds.is_some() {
+        match args.subcommand {
+            Some(Subcommand::Bisect { .. }) => return Err(Error::SeedsSubcommand("bisect")),
+            Some(Subcommand::Repro { .. }) => return Err(Error::SeedsSubcommand("repro")),
+            _ => {}
+        }
+    }
+    // with --num-successes, this starts out as the number of successes and is increased for each failure
+    // with --seeds, this is the number of listed seeds
+    let mut num_seeds = if let Some(ref world_counts) = world_counts {
+        let max_seeds = world_counts.num_seeds();
+        let num_seeds = if let Some(num_seeds) = args.num_seeds.or(args.num_successes).or(seeds.as_ref().map(SeedList::num_seeds)) {
+            num_seeds
+        } else {
+            NonZero::new(SeedIdx::try_from(max_seeds).unwrap_or(SeedIdx::MAX)).expect("world count distribution should be nonempty")
+        };
+        if num_seeds.get() as usize > max_seeds || seeds.as_ref().is_some_and(|seeds| seeds.max() as usize >= max_seeds) {
+            return Err(Error::TooManyWorlds(max_seeds))
+        }
+        num_seeds
+    } else {
+        args.num_seeds.or(args.num_successes).or(seeds.as_ref().map(SeedList::num_seeds)).unwrap_or(if let Some(Subcommand::Find { .. }) = args.subcommand {
+            const { NonZero::<SeedIdx>::new(65535).unwrap() }
+        } else {
+            const { NonZero::<SeedIdx>::new(16384).unwrap() }
+        })
+    };
+    if matches!(args.subcommand, Some(Subcommand::WorldCounts { .. })) && world_counts.is_none() {
+        return Err(Error::NoWorldCounts)
+    }
+    if args.ab_rev.is_some() {
+        match args.subcommand {
+            Some(Subcommand::MidosHouse { .. }) => return Err(Error::AbRevSubcommand("midos-house")),
+            Some(Subcommand::Bisect { .. }) => return Err(Error::AbRevSubcommand("bisect")),
+            Some(Subcommand::Find { .. }) => return Err(Error::AbRevSubcommand("find")),
+            Some(Subcommand::Repro { .. }) => return Err(Error::AbRevSubcommand("repro")),
+            _ => {}
+        }
     }
     let (cli_tx, mut cli_rx) = mpsc::channel(256);
     tokio::spawn(async move {
@@ -482,7 +1353,12 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
         }};
     }
 
-    let is_bench = matches!(args.subcommand, Some(Subcommand::Bench { .. }));
+    let output_mode = match args.subcommand {
+        Some(Subcommand::Bench { uncompressed, .. } | Subcommand::WorldCounts { bench: true, uncompressed }) => OutputMode::Bench { uncompressed },
+        Some(Subcommand::Bisect { instructions: Some(_), .. }) => OutputMode::Bench { uncompressed: false },
+        _ => OutputMode::Normal { patch: args.patch },
+    };
+    let is_bench = matches!(output_mode, OutputMode::Bench { .. });
     let repo = if let Some(repo) = args.repo {
         Cow::Owned(repo)
     } else if args.rsl {
@@ -493,35 +1369,7 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
     let rando_rev = if let Some(rev) = args.rev {
         rev
     } else {
-        let mut dir_parent = gitdir().await?.join("github.com").join(&args.github_user).join(&*repo);
-        let dir_name = if let Some(ref branch) = args.branch {
-            dir_parent = dir_parent.join("branch");
-            branch
-        } else {
-            "main"
-        };
-        let dir = dir_parent.join(dir_name);
-        if fs::exists(&dir).await? {
-            let repo = gix::open(&dir)?;
-            repo.find_default_remote(gix::remote::Direction::Fetch).ok_or(Error::NoDefaultRemote)??
-                .connect(gix::remote::Direction::Fetch)?
-                .prepare_fetch(gix::progress::Discard /*TODO show progress on command line? */, Default::default())?
-                .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(NonZero::<u32>::MIN))
-                .receive(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?;
-            Command::new("git").arg("reset").arg("--hard").arg(format!("origin/{}", args.branch.as_deref().unwrap_or("HEAD"))).current_dir(dir).check("git reset").await?; //TODO use gix, blocked on https://github.com/GitoxideLabs/gitoxide/issues/301
-            repo
-        } else {
-            fs::create_dir_all(&dir_parent).await?;
-            let mut clone = gix::prepare_clone(format!("https://github.com/{}/{repo}.git", args.github_user), dir)?
-                .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(NonZero::<u32>::MIN));
-            if let Some(ref branch) = args.branch {
-                clone = clone.with_ref_name(Some(branch))?;
-            }
-            clone
-                .fetch_then_checkout(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?.0
-                .main_worktree(gix::progress::Discard /*TODO show progress on command line? */, &gix::interrupt::IS_INTERRUPTED)?
-                .0
-        }.head_id()?.detach()
+        update_repo(&args.github_user, &repo, args.branch.as_deref()).await?.1.head_id()?.detach()
     };
     let baseline_rando_rev = 'baseline_rando_rev: {
         if let Some(rev) = args.baseline_rev {
@@ -594,7 +1442,8 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                 settings
             },
             plando: args.plando,
-            world_counts: args.world_counts,
+            world_counts: world_counts.clone(),
+            sweeps: args.sweeps.clone(),
             seeds: if let Some(seed) = args.seed {
                 Seeds::Fixed(seed)
             } else if args.retry_failures {
@@ -604,66 +1453,140 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
             },
         }
     };
-    let stats_root = if let Some(stats_dir) = config.stats_dir.take() {
-        stats_dir
-    } else {
-        #[cfg(windows)] let project_dirs = ProjectDirs::from("net", "Fenhl", "ootrstats").ok_or(Error::MissingHomeDir)?;
-        #[cfg(windows)] { project_dirs.data_dir().to_owned() }
-        #[cfg(unix)] { ootrstats::cache_dir().at_unknown()? }
-    };
+    let stats_root = stats_root(&mut config)?;
     let stats_dir = stats_root.join(setup.stats_dir(rando_rev));
+    let ab_stats_dir = args.ab_rev.map(|ab_rev| stats_root.join(setup.stats_dir(ab_rev)));
     let baseline_stats_dir = baseline_rando_rev.map(|rando_rev| stats_root.join(setup.stats_dir(rando_rev)));
+    if let Some(Subcommand::Repro { seed_idx, ref out_dir }) = args.subcommand {
+        let Some((name, base_rom_path, wsl_distro)) = config.workers.iter()
+            .filter(|worker::Config { name, .. }| args.include_workers.is_empty() || args.include_workers.contains(name))
+            .filter(|worker::Config { name, .. }| !args.exclude_workers.contains(name))
+            .find_map(|worker::Config { name, kind, .. }| if let worker::Kind::Local { base_rom_path, wsl_distro, .. } = kind { Some((name.clone(), base_rom_path, wsl_distro)) } else { None })
+        else { return Err(Error::NoLocalWorker) };
+        // the randomizer runs in its repo, so relative paths would be resolved incorrectly
+        let out_dir = out_dir.clone().unwrap_or_else(|| PathBuf::from(format!("repro-{seed_idx}")));
+        let out_dir = std::path::absolute(&out_dir).at(&out_dir)?;
+        let rsl_plando = stats_dir.join(seed_idx.to_string()).join("random_settings.json");
+        let rsl_plando = if fs::exists(&rsl_plando).await? { Some(rsl_plando) } else { None };
+        let (repro_tx, mut repro_rx) = mpsc::channel(256);
+        let mut repro = pin!(ootrstats::worker::repro(&repro_tx, base_rom_path, wsl_distro.as_deref(), rando_rev, &setup, seed_idx, rsl_plando.as_deref(), &out_dir));
+        let status = loop {
+            select! {
+                res = &mut repro => break res.map_err(|e| Error::Worker { worker_errors: vec![(name.clone(), e.into())], cancelled: false })?,
+                Some(msg) = repro_rx.recv() => if let ootrstats::worker::Message::Init(msg) = msg {
+                    Message::ReproProgress(&msg).print(args.json_messages, &mut stderr)?;
+                },
+            }
+        };
+        Message::Repro { seed_idx, success: status.success(), out_dir }.print(args.json_messages, &mut stdout)?;
+        return Ok(Outcome { cancelled: false, bisect_sample: None })
+    }
     if args.clean {
         fs::remove_dir_all(&stats_dir).await.missing_ok()?;
+        if let Some(ref ab_stats_dir) = ab_stats_dir {
+            fs::remove_dir_all(ab_stats_dir).await.missing_ok()?;
+        }
     }
-    let available_parallelism = if args.world_counts {
+    let ab = args.ab_rev.is_some();
+    let num_slots = num_seeds.get() as usize * if ab { 2 } else { 1 };
+    let available_parallelism = if world_counts.is_some() {
         NonZero::<SeedIdx>::MIN // ensure seeds are started in order
     } else {
-        std::thread::available_parallelism().unwrap_or(NonZero::<usize>::MIN).try_into().unwrap_or(NonZero::<SeedIdx>::MAX).min(args.num_seeds)
+        std::thread::available_parallelism().unwrap_or(NonZero::<usize>::MIN).try_into().unwrap_or(NonZero::<SeedIdx>::MAX).min(num_seeds)
     };
     let start = Instant::now();
     let start_local = Local::now();
-    let mut seed_states = Vec::from_iter(iter::repeat_with(|| SeedState::Unchecked).take(args.num_seeds.get().into()));
-    let mut retried_failures = vec![0; args.num_seeds.get().into()];
+    // with --seeds, the seed states are only for the listed seeds, see nth_seed
+    let mut seed_states = Vec::from_iter(iter::repeat_with(|| SeedState::Unchecked).take(num_slots));
+    let mut retried_failures = vec![0; num_slots];
+    let mut infra_failures = vec![Vec::default(); num_slots];
+    let mut trackers = if args.until_ci.is_some() {
+        let categorize = matches!(args.subcommand, Some(Subcommand::Categorize { .. }));
+        iter::once(Side::A).chain(args.ab_rev.map(|_| Side::B)).map(|side| (side, precision::Tracker::new(categorize))).collect::<HashMap<_, _>>()
+    } else {
+        HashMap::default()
+    };
+    let analysis_spec = analysis::Spec::new(args.subcommand.as_ref()).await?;
+    let analysis_key = analysis_spec.as_ref().map(analysis::Spec::key);
+    let include_failures = analysis_spec.as_ref().is_some_and(analysis::Spec::include_failures);
+    let (extract_tx, mut extract_rx) = mpsc::channel(256);
+    let mut pool = if let Some(ref analysis_spec) = analysis_spec {
+        // compile the queries once here so errors are reported before any seeds are rolled
+        Analysis::new(analysis_spec)?;
+        Some(analysis::Pool::new(analysis_spec, extract_tx))
+    } else {
+        drop(extract_tx);
+        None
+    };
+    let find = matches!(args.subcommand, Some(Subcommand::Find { .. }));
+    let mut found = BTreeSet::default();
     let mut allowed_workers = HashMap::new();
-    let (reader_tx, mut reader_rx) = mpsc::channel(args.num_seeds.get().min(256).into());
-    let mut readers = (0..available_parallelism.get()).map(|task_idx| {
+    let (reader_tx, mut reader_rx) = mpsc::channel(num_seeds.get().min(256) as usize);
+    let spawn_reader = |reader_tx: mpsc::Sender<ReaderMessage>, slots: iter::StepBy<Range<usize>>| {
         let stats_dir = stats_dir.clone();
+        let ab_stats_dir = ab_stats_dir.clone();
         let baseline_stats_dir = baseline_stats_dir.clone();
-        let reader_tx = reader_tx.clone();
+        let world_counts = world_counts.clone();
+        let analysis_key = analysis_key.clone();
+        let seeds = seeds.clone();
+        let seed_timeout = args.seed_timeout;
         tokio::spawn(async move {
-            for seed_idx in (task_idx..args.num_seeds.get()).step_by(available_parallelism.get().into()) {
-                let seed_path = stats_dir.join(seed_idx.to_string());
+            for slot in slots {
+                let (side, seed_idx) = slot_seed(ab, seeds.as_ref(), slot);
+                let seed_path = stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx);
                 let stats_spoiler_log_path = seed_path.join("spoiler.json");
                 let stats_error_log_path = seed_path.join("error.log");
                 match (fs::exists(&stats_spoiler_log_path).await?, fs::exists(&stats_error_log_path).await?) {
-                    (false, false) => reader_tx.send(ReaderMessage::Pending {
-                        allowed_workers: if let Some(ref baseline_stats_dir) = baseline_stats_dir {
-                            let baseline_seed_path = baseline_stats_dir.join(seed_idx.to_string());
-                            match fs::read_json(baseline_seed_path.join("metadata.json")).await {
-                                Ok(Metadata { worker, .. }) => Some(nev![worker]),
-                                Err(wheel::Error::Io { inner, .. }) if inner.kind() == io::ErrorKind::NotFound => None,
-                                Err(e) => return Err(e.into()),
-                            }
+                    (false, false) => if_chain! {
+                        if let Some(stored) = match fs::read_json::<analysis::Stored>(seed_path.join("extracted.json")).await {
+                            Ok(stored) => Some(stored),
+                            Err(wheel::Error::Io { inner, .. }) if inner.kind() == io::ErrorKind::NotFound => None,
+                            Err(e) => return Err(e.into()),
+                        };
+                        // values extracted for a different subcommand or query can't be reused, so the seed is rolled again in that case
+                        if stored.is_reusable(analysis_key.as_deref());
+                        then {
+                            let Metadata { instructions, rsl_instructions, worker, world_count, sweep: _, exit_signal: _, timed_out: _, out_of_memory: _, seed_timeout: _ } = fs::read_json(seed_path.join("metadata.json")).await?;
+                            reader_tx.send(ReaderMessage::Success {
+                                instructions: instructions.and_then(Result::ok),
+                                rsl_instructions: rsl_instructions.and_then(Result::ok),
+                                world_count: world_count.or_else(|| world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx))),
+                                stored: Some(stored),
+                                side, seed_idx, worker,
+                            }).await?;
                         } else {
-                            None
-                        },
-                        seed_idx,
-                    }).await?,
+                            reader_tx.send(ReaderMessage::Pending {
+                                allowed_workers: baseline_worker(baseline_stats_dir.as_deref(), seed_idx).await?,
+                                side, seed_idx,
+                            }).await?;
+                        }
+                    },
                     (false, true) => {
-                        let Metadata { instructions, rsl_instructions, worker } = fs::read_json(seed_path.join("metadata.json")).await?;
-                        reader_tx.send(ReaderMessage::Failure {
-                            instructions: instructions.and_then(Result::ok),
-                            rsl_instructions: rsl_instructions.and_then(Result::ok),
-                            seed_idx, worker,
-                        }).await?;
+                        let Metadata { instructions, rsl_instructions, worker, world_count, sweep: _, exit_signal, timed_out, out_of_memory, seed_timeout: old_seed_timeout } = fs::read_json(seed_path.join("metadata.json")).await?;
+                        if timed_out && seed_timeout.is_none_or(|seed_timeout| old_seed_timeout.is_some_and(|old_seed_timeout| seed_timeout.as_secs() > old_seed_timeout)) {
+                            // the seed might not time out with the current --seed-timeout, so it's rolled again
+                            fs::remove_dir_all(&seed_path).await?;
+                            reader_tx.send(ReaderMessage::Pending {
+                                allowed_workers: baseline_worker(baseline_stats_dir.as_deref(), seed_idx).await?,
+                                side, seed_idx,
+                            }).await?;
+                        } else {
+                            reader_tx.send(ReaderMessage::Failure {
+                                instructions: instructions.and_then(Result::ok),
+                                rsl_instructions: rsl_instructions.and_then(Result::ok),
+                                world_count: world_count.or_else(|| world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx))),
+                                side, seed_idx, worker, exit_signal, timed_out, out_of_memory,
+                            }).await?;
+                        }
                     }
                     (true, false) => {
-                        let Metadata { instructions, rsl_instructions, worker } = fs::read_json(seed_path.join("metadata.json")).await?;
+                        let Metadata { instructions, rsl_instructions, worker, world_count, sweep: _, exit_signal: _, timed_out: _, out_of_memory: _, seed_timeout: _ } = fs::read_json(seed_path.join("metadata.json")).await?;
                         reader_tx.send(ReaderMessage::Success {
                             instructions: instructions.and_then(Result::ok),
                             rsl_instructions: rsl_instructions.and_then(Result::ok),
-                            seed_idx, worker,
+                            world_count: world_count.or_else(|| world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx))),
+                            stored: None,
+                            side, seed_idx, worker,
                         }).await?;
                     }
                     (true, true) => return Err(Error::SuccessAndFailure),
@@ -672,8 +1595,12 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
             reader_tx.send(ReaderMessage::Done).await?;
             Ok(())
         })
-    }).collect::<FuturesUnordered<_>>();
-    drop(reader_tx);
+    };
+    let mut readers = (0..available_parallelism.get())
+        .map(|task_idx| spawn_reader(reader_tx.clone(), (task_idx as usize..num_slots).step_by(available_parallelism.get() as usize)))
+        .collect::<FuturesUnordered<_>>();
+    // with --num-successes, more seeds may have to be read later
+    let mut reader_tx = args.num_successes.is_some().then_some(reader_tx);
     let mut completed_readers = 0;
     let (worker_tx, mut worker_rx) = mpsc::channel(256);
     let mut worker_tx = Some(worker_tx);
@@ -684,8 +1611,30 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
         .filter(|worker::Config { bench, .. }| *bench || !is_bench)
         .map(|worker::Config { name, .. }| worker::State::new(name.clone()))
         .collect_vec();
+    let all_worker_names = workers.iter().map(|worker| worker.name.clone()).collect_vec();
     let mut cancelled = false;
     let mut cancelled_by_user = false;
+    // rendering the status is linear in the sample size, so it's rate limited to keep large runs from spending most of their time on it
+    let mut last_status = None::<Instant>;
+    let mut status_outdated = false;
+
+    macro_rules! print_status {
+        () => {{
+            Message::Status {
+                retry_failures: args.retry_failures,
+                world_counts: world_counts.as_ref(),
+                seeds: seeds.as_ref(),
+                seed_states: &seed_states,
+                allowed_workers: &allowed_workers,
+                retried_failures: &retried_failures,
+                infra_failures: &infra_failures,
+                workers: &workers,
+                label, available_parallelism, completed_readers, start, start_local,
+            }.print(args.json_messages, &mut stderr)?;
+            last_status = Some(Instant::now());
+            status_outdated = false;
+        }};
+    }
 
     macro_rules! cancel {
         ($workers:expr) => {{
@@ -695,7 +1644,7 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
             readers.clear();
             completed_readers = available_parallelism.get();
             reader_rx = mpsc::channel(1).1;
-            for (seed_idx, seed_state) in seed_states.iter_mut().enumerate() {
+            for (slot, seed_state) in seed_states.iter_mut().enumerate() {
                 match seed_state {
                     SeedState::Unchecked | SeedState::Pending => *seed_state = SeedState::Cancelled,
                     SeedState::Rolling { workers: worker_names } => if args.race {
@@ -703,7 +1652,8 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                         for name in worker_names.iter() {
                             if let Some(worker) = $workers.iter().find(|worker| worker.name == *name) {
                                 if let Some(tx) = &worker.supervisor_tx {
-                                    let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(seed_idx.try_into()?)).await;
+                                    let (side, seed_idx) = slot_seed(ab, seeds.as_ref(), slot);
+                                    let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(side, seed_idx)).await;
                                 }
                             } else {
                                 return Err(Error::WorkerNotFound)
@@ -711,7 +1661,7 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                         }
                         *seed_state = SeedState::Cancelled;
                     },
-                    SeedState::Cancelled | SeedState::Success { .. } | SeedState::Failure { .. } => {}
+                    SeedState::Cancelled | SeedState::Success { .. } | SeedState::Failure { .. } | SeedState::InfraFailure => {}
                 }
             }
         }};
@@ -723,6 +1673,8 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
             ReaderMessage(ReaderMessage),
             WorkerDone(Arc<str>, Result<Result<(), worker::Error>, JoinError>),
             WorkerMessage(Arc<str>, ootrstats::worker::Message),
+            Extracted(Result<(usize, analysis::Extracted), Error>),
+            StatusDue,
             End,
         }
 
@@ -740,49 +1692,91 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                     Some(msg) = reader_rx.recv() => Event::ReaderMessage(msg),
                     Some((name, res)) = worker_tasks.next() => Event::WorkerDone(name, res),
                     Some((name, msg)) = worker_rx.recv() => Event::WorkerMessage(name, msg),
+                    Some(res) = extract_rx.recv() => Event::Extracted(res),
+                    () = sleep_until(last_status.unwrap_or(start) + STATUS_INTERVAL), if status_outdated => Event::StatusDue,
                     else => Event::End,
                 })
             } => {
                 match event? {
                     Event::ReaderDone(res) => { let () = res??; }
                     Event::ReaderMessage(msg) => match msg {
-                        ReaderMessage::Pending { seed_idx, allowed_workers: seed_allowed_workers } => {
+                        ReaderMessage::Pending { side, seed_idx, allowed_workers: seed_allowed_workers } => {
+                            let slot = seed_slot(ab, seeds.as_ref(), side, seed_idx);
                             if let Some(seed_allowed_workers) = seed_allowed_workers {
-                                allowed_workers.insert(seed_idx, seed_allowed_workers);
+                                allowed_workers.insert(slot, seed_allowed_workers);
                             }
-                            seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                            seed_states[slot] = SeedState::Pending;
                         }
-                        ReaderMessage::Success { seed_idx, worker, instructions, rsl_instructions } => {
-                            allowed_workers.insert(seed_idx, nev![worker.clone()]);
+                        ReaderMessage::Success { side, seed_idx, worker, instructions, rsl_instructions, world_count, stored } => {
+                            let slot = seed_slot(ab, seeds.as_ref(), side, seed_idx);
+                            let seed_dir = stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx);
+                            allowed_workers.insert(slot, nev![worker.clone()]);
                             if is_bench && instructions.is_none() {
                                 // seed was already rolled but not benchmarked, roll a new seed instead
-                                fs::remove_dir_all(stats_dir.join(seed_idx.to_string())).await?;
-                                seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                                fs::remove_dir_all(seed_dir).await?;
+                                seed_states[slot] = SeedState::Pending;
                             } else {
-                                seed_states[usize::from(seed_idx)] = SeedState::Success {
+                                let extracted = if let Some(stored) = stored {
+                                    stored.extracted
+                                } else {
+                                    if let Some(ref pool) = pool {
+                                        // don't delay rolling new seeds by reading existing spoiler logs
+                                        pool.extract(slot, analysis::Source::Path(seed_dir.join("spoiler.json")), analysis::meta(&seed_dir, seed_idx, &worker, instructions, rsl_instructions, world_count).await?);
+                                    }
+                                    None
+                                };
+                                if let (Some(tracker), Some(extracted)) = (trackers.get_mut(&side), &extracted) {
+                                    tracker.record_extracted(extracted)?;
+                                }
+                                if find && extracted.as_ref().is_some_and(analysis::Extracted::is_match) {
+                                    found.insert(seed_idx);
+                                }
+                                seed_states[slot] = SeedState::Success {
                                     completed_at: None,
-                                    spoiler_log: fs::read_json(stats_dir.join(seed_idx.to_string()).join("spoiler.json")).await?,
-                                    worker, instructions, rsl_instructions,
+                                    worker, instructions, rsl_instructions, world_count, extracted,
                                 };
+                                if let Some(tracker) = trackers.get_mut(&side) {
+                                    tracker.record(&seed_states[slot]);
+                                }
                             }
                         }
-                        ReaderMessage::Failure { worker, seed_idx, instructions, rsl_instructions } => {
-                            let error_log = Bytes::from(fs::read(stats_dir.join(seed_idx.to_string()).join("error.log")).await?);
-                            if args.retry_failures || parse_traceback(&worker, seed_idx, std::str::from_utf8(&error_log)?)?.1.contains("Cannot allocate memory") {
-                                fs::remove_dir_all(stats_dir.join(seed_idx.to_string())).await?;
-                                retried_failures[usize::from(seed_idx)] += 1;
-                                seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                        ReaderMessage::Failure { worker, side, seed_idx, instructions, rsl_instructions, world_count, exit_signal, timed_out, out_of_memory } => {
+                            let slot = seed_slot(ab, seeds.as_ref(), side, seed_idx);
+                            let seed_dir = stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx);
+                            let error_log = Bytes::from(fs::read(seed_dir.join("error.log")).await?);
+                            // timed out seeds are killed with SIGKILL and seeds exceeding the memory limit fail to allocate, either of which would otherwise match the default infrastructure failure rules
+                            let is_infra_failure = !timed_out && !out_of_memory && config.is_infra_failure(exit_signal, &error_log);
+                            if is_infra_failure || args.retry_failures {
+                                fs::remove_dir_all(seed_dir).await?;
+                                if is_infra_failure {
+                                    infra_failures[slot].push(worker);
+                                    seed_states[slot] = after_infra_failure(&allowed_workers, &infra_failures, &all_worker_names, slot);
+                                } else {
+                                    retried_failures[slot] += 1;
+                                    seed_states[slot] = SeedState::Pending;
+                                }
                             } else {
-                                allowed_workers.insert(seed_idx, nev![worker.clone()]);
-                                if is_bench && instructions.is_none() {
+                                allowed_workers.insert(slot, nev![worker.clone()]);
+                                if is_bench && instructions.is_none() && !timed_out {
                                     // seed was already rolled but not benchmarked, roll a new seed instead
-                                    fs::remove_dir_all(stats_dir.join(seed_idx.to_string())).await?;
-                                    seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                                    fs::remove_dir_all(seed_dir).await?;
+                                    seed_states[slot] = SeedState::Pending;
                                 } else {
-                                    seed_states[usize::from(seed_idx)] = SeedState::Failure {
+                                    if_chain! {
+                                        if include_failures;
+                                        if let Some(ref pool) = pool;
+                                        then {
+                                            pool.extract(slot, analysis::Source::failure(&error_log)?, analysis::meta(&seed_dir, seed_idx, &worker, instructions, rsl_instructions, world_count).await?);
+                                        }
+                                    }
+                                    seed_states[slot] = SeedState::Failure {
                                         completed_at: None,
-                                        worker, instructions, rsl_instructions, error_log,
+                                        extracted: None,
+                                        worker, instructions, rsl_instructions, world_count, timed_out, out_of_memory, error_log,
                                     };
+                                    if let Some(tracker) = trackers.get_mut(&side) {
+                                        tracker.record(&seed_states[slot]);
+                                    }
                                 }
                             }
                         }
@@ -831,8 +1825,8 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                                     worker.prev_error = None;
                                 }
                             }
-                            ootrstats::worker::Message::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => if let SeedState::Rolling { workers: ref mut worker_names } = seed_states[usize::from(seed_idx)] {
-                                let seed_dir = stats_dir.join(seed_idx.to_string());
+                            ootrstats::worker::Message::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => if let SeedState::Rolling { workers: ref mut worker_names } = seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] {
+                                let seed_dir = stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx);
                                 fs::create_dir_all(&seed_dir).await?;
                                 let stats_spoiler_log_path = seed_dir.join("spoiler.json");
                                 match spoiler_log {
@@ -912,6 +1906,12 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                                     instructions: Some(instructions.as_ref().copied().map_err(|stderr| String::from_utf8_lossy(stderr).into_owned())),
                                     rsl_instructions: Some(rsl_instructions.as_ref().copied().map_err(|stderr| String::from_utf8_lossy(stderr).into_owned())),
                                     worker: name.clone(),
+                                    world_count: world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx)),
+                                    sweep: ootrstats::sweep_point(&args.sweeps, ootrstats::sweep_point_idx(&args.sweeps, seed_idx)),
+                                    exit_signal: None,
+                                    timed_out: false,
+                                    out_of_memory: false,
+                                    seed_timeout: args.seed_timeout.map(|seed_timeout| seed_timeout.as_secs()),
                                 }).await?;
                                 let mut new_workers = Vec::from(worker_names.clone());
                                 let Some(pos) = new_workers.iter().position(|worker| *worker == name) else { panic!("got success from a worker ({name}) that wasn't rolling that seed ({seed_idx})") };
@@ -928,46 +1928,67 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                                         if let Some(new_workers) = NEVec::try_from_vec(new_workers) {
                                             *worker_names = new_workers;
                                         } else {
-                                            seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                                            seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = SeedState::Pending;
                                         }
                                     } else {
                                         // cancel remaining raced copies of this seed
                                         for name in new_workers {
                                             if let Some(worker) = workers.iter().find(|worker| worker.name == name) {
                                                 if let Some(tx) = &worker.supervisor_tx {
-                                                    let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(seed_idx)).await;
+                                                    let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(side, seed_idx)).await;
                                                 }
                                             } else {
                                                 return Err(Error::WorkerNotFound)
                                             }
                                         }
-                                        seed_states[usize::from(seed_idx)] = SeedState::Success {
+                                        let instructions = instructions.as_ref().ok().copied();
+                                        let rsl_instructions = rsl_instructions.as_ref().ok().copied();
+                                        let world_count = world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx));
+                                        if let Some(ref pool) = pool {
+                                            // the spoiler log is discarded (if requested) once the values have been extracted from it
+                                            pool.extract(seed_slot(ab, seeds.as_ref(), side, seed_idx), match spoiler_log {
+                                                Either::Left(_) => analysis::Source::Path(seed_dir.join("spoiler.json")),
+                                                Either::Right(spoiler_log) => analysis::Source::Bytes(spoiler_log),
+                                            }, analysis::meta(&seed_dir, seed_idx, &name, instructions, rsl_instructions, world_count).await?);
+                                        } else if args.discard_logs {
+                                            analysis::discard_log(&seed_dir, None, None).await?;
+                                        }
+                                        seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = SeedState::Success {
                                             completed_at: Some(Instant::now()),
                                             worker: name,
-                                            spoiler_log: match spoiler_log {
-                                                Either::Left(_) => fs::read_json(stats_dir.join(seed_idx.to_string()).join("spoiler.json")).await?,
-                                                Either::Right(spoiler_log) => serde_json::from_slice(&spoiler_log)?,
-                                            },
-                                            instructions: instructions.as_ref().ok().copied(),
-                                            rsl_instructions: rsl_instructions.as_ref().ok().copied(),
+                                            extracted: None,
+                                            instructions, rsl_instructions, world_count,
                                         };
+                                        if let Some(tracker) = trackers.get_mut(&side) {
+                                            tracker.record(&seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)]);
+                                        }
                                     }
                                 }
                             } else {
                                 // seed was already rolled but this worker's instance of this seed didn't get cancelled in time so we just ignore it
                             },
-                            ootrstats::worker::Message::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando } => if let SeedState::Rolling { workers: ref mut worker_names } = seed_states[usize::from(seed_idx)] {
-                                let seed_dir = stats_dir.join(seed_idx.to_string());
+                            ootrstats::worker::Message::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando } => if let SeedState::Rolling { workers: ref mut worker_names } = seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] {
+                                let seed_dir = stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx);
                                 let mut new_workers = Vec::from(worker_names.clone());
                                 let pos = new_workers.iter().position(|worker| *worker == name).expect("got failure from a worker that wasn't rolling that seed");
                                 new_workers.swap_remove(pos);
-                                if args.retry_failures || parse_traceback(&name, seed_idx, std::str::from_utf8(&error_log)?)?.1.contains("Cannot allocate memory") {
+                                let is_infra_failure = !timed_out && !out_of_memory && config.is_infra_failure(exit_signal, &error_log);
+                                if is_infra_failure || args.retry_failures {
                                     fs::remove_dir_all(seed_dir).await.missing_ok()?;
-                                    retried_failures[usize::from(seed_idx)] += 1;
+                                    if is_infra_failure {
+                                        // the seed is rolled again on a different worker if possible, see may_roll and after_infra_failure
+                                        log!("worker {name} had an infrastructure failure on seed {seed_idx}, exit signal: {exit_signal:?}, error log:");
+                                        log!("{}", String::from_utf8_lossy(&error_log));
+                                        infra_failures[seed_slot(ab, seeds.as_ref(), side, seed_idx)].push(name.clone());
+                                    } else {
+                                        retried_failures[seed_slot(ab, seeds.as_ref(), side, seed_idx)] += 1;
+                                    }
                                     if let Some(new_workers) = NEVec::try_from_vec(new_workers) {
                                         *worker_names = new_workers;
+                                    } else if is_infra_failure {
+                                        seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = after_infra_failure(&allowed_workers, &infra_failures, &all_worker_names, seed_slot(ab, seeds.as_ref(), side, seed_idx));
                                     } else {
-                                        seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                                        seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = SeedState::Pending;
                                     }
                                 } else {
                                     fs::create_dir_all(&seed_dir).await?;
@@ -996,10 +2017,15 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                                         instructions: Some(instructions.as_ref().copied().map_err(|stderr| String::from_utf8_lossy(stderr).into_owned())),
                                         rsl_instructions: Some(rsl_instructions.as_ref().copied().map_err(|stderr| String::from_utf8_lossy(stderr).into_owned())),
                                         worker: name.clone(),
+                                        world_count: world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx)),
+                                        sweep: ootrstats::sweep_point(&args.sweeps, ootrstats::sweep_point_idx(&args.sweeps, seed_idx)),
+                                        seed_timeout: args.seed_timeout.map(|seed_timeout| seed_timeout.as_secs()),
+                                        exit_signal, timed_out, out_of_memory,
                                     }).await?;
                                     if_chain! {
                                         if !cancelled;
                                         if is_bench;
+                                        if !timed_out;
                                         if let Some(ref stderr) = instructions.as_ref().err().or_else(|| rsl_instructions.as_ref().err());
                                         then {
                                             // perf sometimes doesn't output instruction count for whatever reason, retry if this happens
@@ -1009,26 +2035,38 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                                             if let Some(new_workers) = NEVec::try_from_vec(new_workers) {
                                                 *worker_names = new_workers;
                                             } else {
-                                                seed_states[usize::from(seed_idx)] = SeedState::Pending;
+                                                seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = SeedState::Pending;
                                             }
                                         } else {
                                             // cancel remaining raced copies of this seed
                                             for name in new_workers {
                                                 if let Some(worker) = workers.iter().find(|worker| worker.name == name) {
                                                     if let Some(tx) = &worker.supervisor_tx {
-                                                        let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(seed_idx)).await;
+                                                        let _ = tx.send(ootrstats::worker::SupervisorMessage::Cancel(side, seed_idx)).await;
                                                     }
                                                 } else {
                                                     return Err(Error::WorkerNotFound)
                                                 }
                                             }
-                                            seed_states[usize::from(seed_idx)] = SeedState::Failure {
+                                            let instructions = instructions.as_ref().ok().copied();
+                                            let rsl_instructions = rsl_instructions.as_ref().ok().copied();
+                                            let world_count = world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx));
+                                            if_chain! {
+                                                if include_failures;
+                                                if let Some(ref pool) = pool;
+                                                then {
+                                                    pool.extract(seed_slot(ab, seeds.as_ref(), side, seed_idx), analysis::Source::failure(&error_log)?, analysis::meta(&seed_dir, seed_idx, &name, instructions, rsl_instructions, world_count).await?);
+                                                }
+                                            }
+                                            seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)] = SeedState::Failure {
                                                 completed_at: Some(Instant::now()),
                                                 worker: name,
-                                                instructions: instructions.as_ref().ok().copied(),
-                                                rsl_instructions: rsl_instructions.as_ref().ok().copied(),
-                                                error_log,
+                                                extracted: None,
+                                                instructions, rsl_instructions, world_count, timed_out, out_of_memory, error_log,
                                             };
+                                            if let Some(tracker) = trackers.get_mut(&side) {
+                                                tracker.record(&seed_states[seed_slot(ab, seeds.as_ref(), side, seed_idx)]);
+                                            }
                                         }
                                     }
                                 }
@@ -1039,40 +2077,98 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                     } else {
                         return Err(Error::WorkerNotFound)
                     },
+                    Event::Extracted(res) => {
+                        let (slot, values) = res?;
+                        let (side, seed_idx) = slot_seed(ab, seeds.as_ref(), slot);
+                        match seed_states[slot] {
+                            SeedState::Success { completed_at, ref mut extracted, .. } => {
+                                // --until-ci only looks at the query outputs of successful seeds, even with --include-failures
+                                if let Some(tracker) = trackers.get_mut(&side) {
+                                    tracker.record_extracted(&values)?;
+                                }
+                                let is_match = find && values.is_match();
+                                if is_match {
+                                    found.insert(seed_idx);
+                                }
+                                // the find subcommand keeps the spoiler logs of matching seeds and only those
+                                *extracted = if completed_at.is_some() && if find { !is_match } else { args.discard_logs } {
+                                    analysis::discard_log(&stats_seed_dir(&stats_dir, ab_stats_dir.as_deref(), side, seed_idx), analysis_key.as_deref(), Some(values)).await?
+                                } else {
+                                    Some(values)
+                                };
+                            }
+                            SeedState::Failure { ref mut extracted, .. } => *extracted = Some(values),
+                            SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } | SeedState::Cancelled | SeedState::InfraFailure => {}
+                        }
+                    }
+                    Event::StatusDue => {}
                     Event::End => break,
                 };
-                let pending_seeds = seed_states.iter().enumerate().filter(|(_, state)| matches!(state, SeedState::Pending) || args.race && matches!(state, SeedState::Rolling { .. })).map(|(seed_idx, _)| seed_idx as SeedIdx).collect::<HashSet<_>>();
+                if completed_readers == available_parallelism.get() && (
+                    args.until_ci.is_some_and(|until_ci| trackers.values().all(|tracker| tracker.half_width().is_some_and(|half_width| half_width <= until_ci)))
+                    || matches!(args.subcommand, Some(Subcommand::Find { count, .. }) if found.len() >= count.get())
+                    || args.num_successes.is_some_and(|num_successes| iter::once(Side::A).chain(args.ab_rev.map(|_| Side::B)).all(|side| count_seeds(ab, &seed_states, side, |state| matches!(state, SeedState::Success { .. })) >= num_successes.get() as usize))
+                ) {
+                    // target precision or number of matching or successful seeds reached, finish rolling seeds that are already in progress but don't start any more
+                    for state in &mut seed_states {
+                        if let SeedState::Pending = state {
+                            *state = SeedState::Cancelled;
+                        }
+                    }
+                }
+                if_chain! {
+                    if let Some(num_successes) = args.num_successes;
+                    if !cancelled;
+                    if completed_readers == available_parallelism.get();
+                    if let Some(ref reader_tx) = reader_tx;
+                    // seeds which have succeeded or may still succeed, for the revision which needs the most additional seeds
+                    if let Some(potential_successes) = iter::once(Side::A).chain(args.ab_rev.map(|_| Side::B)).map(|side| count_seeds(ab, &seed_states, side, |state| matches!(state, SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } | SeedState::Success { .. }))).min();
+                    let max_seeds = world_counts.as_ref().map_or(SeedIdx::MAX as usize, |world_counts| world_counts.num_seeds().min(SeedIdx::MAX as usize));
+                    let new_num_seeds = (num_seeds.get() as usize + (num_successes.get() as usize).saturating_sub(potential_successes)).min(max_seeds);
+                    if new_num_seeds > num_seeds.get() as usize;
+                    then {
+                        // extend the range of seeds to make up for failures
+                        let old_num_slots = seed_states.len();
+                        num_seeds = NonZero::new(new_num_seeds as SeedIdx).expect("number of seeds was increased");
+                        let num_slots = new_num_seeds * if ab { 2 } else { 1 };
+                        seed_states.resize_with(num_slots, || SeedState::Unchecked);
+                        retried_failures.resize(num_slots, 0);
+                        infra_failures.resize_with(num_slots, Vec::default);
+                        // the new seeds are read by a single additional reader, which is counted as if it were one of the original readers that hasn't finished yet
+                        completed_readers -= 1;
+                        readers.push(spawn_reader(reader_tx.clone(), (old_num_slots..num_slots).step_by(1)));
+                    }
+                }
+                let pending_seeds = seed_states.iter().enumerate().filter(|(_, state)| matches!(state, SeedState::Pending) || args.race && matches!(state, SeedState::Rolling { .. })).map(|(slot, _)| slot).collect::<HashSet<_>>();
                 if !pending_seeds.is_empty() {
                     if let Some(worker_tx) = &worker_tx {
                         for worker in &mut workers {
-                            if worker.supervisor_tx.is_none() && !worker.stopped && pending_seeds.iter().any(|seed_idx| allowed_workers.get(seed_idx).is_none_or(|allowed_workers| allowed_workers.contains(&worker.name))) {
-                                let worker::Config { name, kind, min_disk, min_disk_percent, min_disk_mount_points, .. } = config.workers.iter().find(|config| config.name == worker.name).expect("unconfigured worker");
-                                worker_tasks.push(worker.connect(worker_tx.clone(), kind.clone(), rando_rev, &setup, if let Some(Subcommand::Bench { uncompressed, .. }) = args.subcommand {
-                                    OutputMode::Bench { uncompressed }
-                                } else {
-                                    OutputMode::Normal { patch: args.patch }
-                                }, *min_disk, *min_disk_percent, min_disk_mount_points.clone(), args.race).map(move |res| (name.clone(), res)));
+                            if worker.supervisor_tx.is_none() && !worker.stopped && pending_seeds.iter().any(|&slot| may_roll(&allowed_workers, &infra_failures, slot, &worker.name)) {
+                                let worker::Config { name, kind, min_disk, min_disk_percent, min_disk_mount_points, memory_limit, .. } = config.workers.iter().find(|config| config.name == worker.name).expect("unconfigured worker");
+                                worker_tasks.push(worker.connect(worker_tx.clone(), kind.clone(), rando_rev, args.ab_rev, &setup, output_mode, args.seed_timeout, *memory_limit, *min_disk, *min_disk_percent, min_disk_mount_points.clone(), args.race).map(move |res| (name.clone(), res)));
                             }
                         }
                     }
                 }
                 'outer: for worker in &mut workers {
                     while worker.error.is_none() && worker.ready > 0 {
-                        if let Some((seed_idx, _)) = seed_states.iter().enumerate().find(|(seed_idx, state)| matches!(state, SeedState::Pending) && allowed_workers.get(&(*seed_idx as SeedIdx)).is_none_or(|allowed_workers| allowed_workers.contains(&worker.name))) {
+                        if let Some((slot, _)) = seed_states.iter().enumerate().find(|&(slot, state)| matches!(state, SeedState::Pending) && may_roll(&allowed_workers, &infra_failures, slot, &worker.name)) {
+                            let (side, seed_idx) = slot_seed(ab, seeds.as_ref(), slot);
                             log!("assigning pending seed {seed_idx} to worker {}", worker.name);
-                            if let Err(mpsc::error::SendError(message)) = worker.roll(&mut seed_states, seed_idx.try_into()?).await {
+                            if let Err(mpsc::error::SendError(message)) = worker.roll(&mut seed_states[slot], side, seed_idx).await {
                                 worker.error.get_or_insert(worker::Error::Receive { message });
                                 cancel!(workers);
                                 break 'outer
                             }
                         } else if args.race && completed_readers == available_parallelism.get() { // don't assign the same seed multiple times if there might still be pending seeds
-                            let seed_idx = seed_states.iter()
+                            let slot = seed_states.iter()
                                 .enumerate()
-                                .filter_map(|(seed_idx, state)| if let SeedState::Rolling { workers } = state { Some((seed_idx, workers.len())) } else { None })
+                                .filter_map(|(slot, state)| if let SeedState::Rolling { workers } = state { Some((slot, workers.len())) } else { None })
                                 .min_by_key(|&(_, num_workers)| num_workers);
-                            if let Some((seed_idx, num_workers)) = seed_idx {
+                            if let Some((slot, num_workers)) = slot {
+                                let (side, seed_idx) = slot_seed(ab, seeds.as_ref(), slot);
                                 log!("--race: adding worker {} to seed {seed_idx} (which is already running {num_workers} times)", worker.name);
-                                if let Err(mpsc::error::SendError(message)) = worker.roll(&mut seed_states, seed_idx.try_into()?).await {
+                                if let Err(mpsc::error::SendError(message)) = worker.roll(&mut seed_states[slot], side, seed_idx).await {
                                     worker.error.get_or_insert(worker::Error::Receive { message });
                                     cancel!(workers);
                                     break 'outer
@@ -1095,19 +2191,16 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
                 }
             }
         }
-        Message::Status {
-            retry_failures: args.retry_failures,
-            world_counts: args.world_counts,
-            seed_states: &seed_states,
-            allowed_workers: &allowed_workers,
-            retried_failures: &retried_failures,
-            workers: &workers,
-            label, available_parallelism, completed_readers, start, start_local,
-        }.print(args.json_messages, &mut stderr)?;
-        if completed_readers == available_parallelism.get() && seed_states.iter().all(|state| match state {
-            SeedState::Cancelled | SeedState::Success { .. } | SeedState::Failure { .. } => true,
+        let all_done = completed_readers == available_parallelism.get() && seed_states.iter().all(|state| match state {
+            SeedState::Cancelled | SeedState::Success { .. } | SeedState::Failure { .. } | SeedState::InfraFailure => true,
             SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => false,
-        }) {
+        });
+        if all_done || last_status.is_none_or(|last_status| last_status.elapsed() >= STATUS_INTERVAL) {
+            print_status!();
+        } else {
+            status_outdated = true;
+        }
+        if all_done {
             for worker in &mut workers {
                 // drop sender so the worker can shut down
                 worker.supervisor_tx = None;
@@ -1115,137 +2208,375 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
             }
             // make sure worker_tx is dropped to prevent deadlock
             worker_tx = None;
+            // no more seeds will be read, so let reader_rx close
+            reader_tx = None;
+            // let the analysis threads exit once they're done with the queued spoiler logs
+            pool = None;
         }
     }
+    if status_outdated {
+        print_status!();
+    }
     drop(cli_rx);
-    Message::Done { label, num_workers: workers.len() as u16, stats_dir }.print(args.json_messages, &mut stderr)?;
-    match args.subcommand {
-        None => {}
-        Some(Subcommand::Bench { raw_data: false, uncompressed: _ }) => {
-            let mut num_successes = 0u16;
-            let mut num_failures = 0u16;
-            let mut instructions_success = 0u64;
-            let mut instructions_failure = 0u64;
-            let mut rsl_instructions_success = 0u64;
-            let mut rsl_instructions_failure = 0u64;
-            for state in seed_states {
-                match state {
-                    SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
-                    SeedState::Cancelled | SeedState::Success { instructions: None, .. } | SeedState::Failure { instructions: None, .. } => {}
-                    SeedState::Success { instructions: Some(instructions), rsl_instructions, .. } => {
-                        num_successes += 1;
-                        instructions_success += instructions;
-                        rsl_instructions_success += rsl_instructions.unwrap_or_default();
+    Message::Done { label, num_workers: workers.len() as u16, stats_dir: stats_dir.clone(), ab_stats_dir: ab_stats_dir.clone() }.print(args.json_messages, &mut stderr)?;
+    let infra_failure_counts = infra_failures.into_iter().flatten().counts().into_iter().sorted_unstable().collect_vec();
+    if !infra_failure_counts.is_empty() {
+        Message::InfraFailures(infra_failure_counts).print(args.json_messages, &mut stderr)?;
+    }
+    if let Some(until_ci) = args.until_ci {
+        for (side, rev) in iter::once((Side::A, rando_rev)).chain(args.ab_rev.map(|ab_rev| (Side::B, ab_rev))) {
+            let tracker = &trackers[&side];
+            Message::Precision {
+                rev: ab.then(|| rev.to_string()),
+                num_seeds: tracker.num_seeds,
+                half_width: tracker.half_width(),
+                target: until_ci,
+                confidence: precision::CONFIDENCE,
+            }.print(args.json_messages, &mut stdout)?;
+        }
+    }
+    let mut bisect_sample = None;
+    let mut sides = iter::once((Side::A, rando_rev)).chain(args.ab_rev.map(|ab_rev| (Side::B, ab_rev))).map(|(side, rev)| (side, rev, Vec::default())).collect_vec();
+    for (slot, state) in seed_states.into_iter().enumerate() {
+        let side = slot_side(ab, slot);
+        sides.iter_mut().find(|(iter_side, _, _)| *iter_side == side).expect("seed state for a revision that isn't being rolled").2.push(state);
+    }
+    // with --ab-rev, both revisions' results are displayed and recorded before a failed bench gate is reported
+    let mut failed_verdicts = Vec::default();
+    for (_, rando_rev, seed_states) in sides {
+        if ab && args.subcommand.is_some() {
+            Message::AbRev { rev: rando_rev.to_string() }.print(args.json_messages, &mut stdout)?;
+        }
+        let infra_failure_seeds = seed_states.iter().positions(|state| matches!(state, SeedState::InfraFailure)).map(|pos| nth_seed(seeds.as_ref(), pos)).collect_vec();
+        if args.subcommand.is_some() && !infra_failure_seeds.is_empty() {
+            Message::InfraFailureSeeds { seed_idxs: infra_failure_seeds }.print(args.json_messages, &mut stdout)?;
+        }
+        match args.subcommand {
+            None => {}
+            Some(Subcommand::Bench { raw_data: false, uncompressed: _ }) => {
+                for (sweep_point, seed_states) in group_by_sweep_point(&args.sweeps, seeds.as_ref(), seed_states) {
+                    if let Some(ref settings) = sweep_point {
+                        Message::SweepPoint { settings: settings.clone() }.print(args.json_messages, &mut stdout)?;
                     }
-                    SeedState::Failure { instructions: Some(instructions), rsl_instructions, .. } => {
-                        num_failures += 1;
-                        instructions_failure += instructions;
-                        rsl_instructions_failure += rsl_instructions.unwrap_or_default();
+                    let mut num_successes = 0u32;
+                    let mut num_failures = 0u32;
+                    let mut instructions_success = 0u64;
+                    let mut instructions_failure = 0u64;
+                    let mut rsl_instructions_success = 0u64;
+                    let mut rsl_instructions_failure = 0u64;
+                    // seeds outside of --seeds aren't compared against the baseline
+                    let mut seed_idxs = Vec::default();
+                    // (1 or 0 for success or failure, instructions) for each benchmarked seed
+                    let mut samples = Vec::default();
+                    for (seed_idx, state) in seed_states {
+                        if !matches!(state, SeedState::Cancelled | SeedState::InfraFailure) {
+                            seed_idxs.push(seed_idx);
+                        }
+                        match state {
+                            SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
+                            SeedState::Cancelled | SeedState::InfraFailure | SeedState::Success { instructions: None, .. } | SeedState::Failure { instructions: None, .. } => {}
+                            SeedState::Success { instructions: Some(instructions), rsl_instructions, .. } => {
+                                num_successes += 1;
+                                instructions_success += instructions;
+                                rsl_instructions_success += rsl_instructions.unwrap_or_default();
+                                samples.push((1.0, instructions as f64));
+                            }
+                            SeedState::Failure { instructions: Some(instructions), rsl_instructions, .. } => {
+                                num_failures += 1;
+                                instructions_failure += instructions;
+                                rsl_instructions_failure += rsl_instructions.unwrap_or_default();
+                                samples.push((0.0, instructions as f64));
+                            }
+                        }
+                    }
+                    if num_successes == 0 {
+                        Message::InstructionsNoSuccesses.print(args.json_messages, &mut stdout)?;
+                    } else {
+                        let success_rate = num_successes as f64 / (num_successes as f64 + num_failures as f64);
+                        let average_instructions_success = instructions_success / u64::try_from(num_successes).unwrap();
+                        let average_instructions_failure = instructions_failure.checked_div(u64::try_from(num_failures).unwrap()).unwrap_or_default();
+                        let average_failure_count = (1.0 - success_rate) / success_rate; // mean of 0-support geometric distribution
+                        let average_instructions = average_failure_count * average_instructions_failure as f64 + average_instructions_success as f64;
+                        Message::Instructions { rsl: false, num_successes, num_failures, success_rate, average_instructions_success, average_instructions_failure, average_failure_count, average_instructions }.print(args.json_messages, &mut stdout)?;
+                        let rsl_average_instructions = if rsl_instructions_success + rsl_instructions_failure > 0 {
+                            let average_instructions_success = rsl_instructions_success / u64::try_from(num_successes).unwrap();
+                            let average_instructions_failure = rsl_instructions_failure.checked_div(u64::try_from(num_failures).unwrap()).unwrap_or_default();
+                            let average_instructions = average_failure_count * average_instructions_failure as f64 + average_instructions_success as f64;
+                            Message::Instructions { rsl: true, num_successes, num_failures, success_rate, average_instructions_success, average_instructions_failure, average_failure_count, average_instructions }.print(args.json_messages, &mut stdout)?;
+                            Some(average_instructions)
+                        } else {
+                            None
+                        };
+                        if args.sweeps.is_empty() {
+                            let (RandoSetup::Normal { github_user, repo, .. } | RandoSetup::Rsl { github_user, repo, .. }) = &setup;
+                            history::record(&stats_root, history::Entry {
+                                github_user: github_user.clone(),
+                                repo: repo.clone(),
+                                rev: rando_rev.to_string(),
+                                label: label.map(str::to_owned),
+                                date: Utc::now(),
+                                num_successes, num_failures, success_rate, average_instructions_success, average_instructions_failure, average_instructions, rsl_average_instructions,
+                            }).await?;
+                        }
+                    }
+                    if_chain! {
+                        if args.max_regression.is_some() || args.max_failure_rate.is_some();
+                        if let Some(ref baseline_stats_dir) = baseline_stats_dir;
+                        then {
+                            let baseline_samples = read_bench_samples(baseline_stats_dir, &seed_idxs).await?;
+                            if baseline_samples.is_empty() { return Err(Error::NoBaselineBench) }
+                            let baseline_num_failures = baseline_samples.iter().filter(|&&(success, _)| success == 0.0).count();
+                            let average_instructions = stats::ratio_interval(&samples, BENCH_GATE_CONFIDENCE);
+                            let baseline_average_instructions = stats::ratio_interval(&baseline_samples, BENCH_GATE_CONFIDENCE);
+                            let regression = Option::zip(average_instructions, baseline_average_instructions).map(|(new, old)| stats::relative_change_interval(new, old));
+                            let failure_rate = stats::wilson_interval(num_failures as usize, samples.len(), BENCH_GATE_CONFIDENCE);
+                            let baseline_failure_rate = stats::wilson_interval(baseline_num_failures, baseline_samples.len(), BENCH_GATE_CONFIDENCE);
+                            let failure_rate_increase = Option::zip(failure_rate, baseline_failure_rate).map(|(new, old)| stats::difference_interval(new, old));
+                            let regressed = args.max_regression.is_some_and(|max_regression| if let Some(regression) = regression {
+                                regression.lower > max_regression
+                            } else {
+                                // no successful seeds means infinite instructions until success
+                                num_successes == 0 && num_failures > 0 && baseline_num_failures < baseline_samples.len()
+                            });
+                            let failed_more = args.max_failure_rate.is_some_and(|max_failure_rate| failure_rate_increase.is_some_and(|failure_rate_increase| failure_rate_increase.lower > max_failure_rate));
+                            let verdict = BenchVerdict {
+                                passed: !regressed && !failed_more,
+                                label: label.map(str::to_owned),
+                                rev: rando_rev.to_string(),
+                                settings: sweep_point,
+                                baseline_rev: baseline_rando_rev.map(|rev| rev.to_string()),
+                                confidence: BENCH_GATE_CONFIDENCE,
+                                max_regression: args.max_regression,
+                                max_failure_rate: args.max_failure_rate,
+                                average_instructions, baseline_average_instructions, regression, failure_rate, baseline_failure_rate, failure_rate_increase,
+                            };
+                            Message::BenchVerdict(&verdict).print(args.json_messages, &mut stdout)?;
+                            if !verdict.passed {
+                                failed_verdicts.push(verdict);
+                            }
+                        }
                     }
                 }
             }
-            if num_successes == 0 {
-                Message::InstructionsNoSuccesses.print(args.json_messages, &mut stdout)?;
-            } else {
-                let success_rate = num_successes as f64 / (num_successes as f64 + num_failures as f64);
-                let average_instructions_success = instructions_success / u64::try_from(num_successes).unwrap();
-                let average_instructions_failure = instructions_failure.checked_div(u64::try_from(num_failures).unwrap()).unwrap_or_default();
-                let average_failure_count = (1.0 - success_rate) / success_rate; // mean of 0-support geometric distribution
-                let average_instructions = average_failure_count * average_instructions_failure as f64 + average_instructions_success as f64;
-                Message::Instructions { rsl: false, num_successes, num_failures, success_rate, average_instructions_success, average_instructions_failure, average_failure_count, average_instructions }.print(args.json_messages, &mut stdout)?;
-                if rsl_instructions_success + rsl_instructions_failure > 0 {
-                    let average_instructions_success = rsl_instructions_success / u64::try_from(num_successes).unwrap();
-                    let average_instructions_failure = rsl_instructions_failure.checked_div(u64::try_from(num_failures).unwrap()).unwrap_or_default();
-                    let average_instructions = average_failure_count * average_instructions_failure as f64 + average_instructions_success as f64;
-                    Message::Instructions { rsl: true, num_successes, num_failures, success_rate, average_instructions_success, average_instructions_failure, average_failure_count, average_instructions }.print(args.json_messages, &mut stdout)?;
+            Some(Subcommand::Bench { raw_data: true, uncompressed: _ }) => {
+                for (sweep_point, seed_states) in group_by_sweep_point(&args.sweeps, seeds.as_ref(), seed_states) {
+                    if let Some(settings) = sweep_point {
+                        Message::SweepPoint { settings }.print(args.json_messages, &mut stdout)?;
+                    }
+                    for (_, state) in seed_states {
+                        match state {
+                            SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
+                            SeedState::Cancelled | SeedState::InfraFailure | SeedState::Success { instructions: None, .. } | SeedState::Failure { instructions: None, .. } => {}
+                            SeedState::Success { worker, instructions: Some(instructions), rsl_instructions, .. } => {
+                                crossterm::execute!(stdout,
+                                    Print(format_args!("s {instructions} {worker}\r\n")),
+                                ).at_unknown()?;
+                                if let Some(rsl_instructions) = rsl_instructions {
+                                    crossterm::execute!(stdout,
+                                        Print(format_args!("S {rsl_instructions} {worker}\r\n")),
+                                    ).at_unknown()?;
+                                }
+                            }
+                            SeedState::Failure { worker, instructions: Some(instructions), rsl_instructions, .. } => {
+                                crossterm::execute!(stdout,
+                                    Print(format_args!("f {instructions} {worker}\r\n")),
+                                ).at_unknown()?;
+                                if let Some(rsl_instructions) = rsl_instructions {
+                                    crossterm::execute!(stdout,
+                                        Print(format_args!("F {rsl_instructions} {worker}\r\n")),
+                                    ).at_unknown()?;
+                                }
+                            }
+                        }
+                    }
                 }
             }
-        }
-        Some(Subcommand::Bench { raw_data: true, uncompressed: _ }) => {
-            for state in seed_states {
-                match state {
-                    SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
-                    SeedState::Cancelled | SeedState::Success { instructions: None, .. } | SeedState::Failure { instructions: None, .. } => {}
-                    SeedState::Success { worker, instructions: Some(instructions), rsl_instructions, .. } => {
-                        crossterm::execute!(stdout,
-                            Print(format_args!("s {instructions} {worker}\r\n")),
-                        ).at_unknown()?;
-                        if let Some(rsl_instructions) = rsl_instructions {
-                            crossterm::execute!(stdout,
-                                Print(format_args!("S {rsl_instructions} {worker}\r\n")),
-                            ).at_unknown()?;
+            Some(Subcommand::Categorize { histogram, bins, .. }) => if let Some(analysis::Spec::Query { ref queries, .. }) = analysis_spec {
+                for (sweep_point, seed_states) in group_by_sweep_point(&args.sweeps, seeds.as_ref(), seed_states) {
+                    if let Some(settings) = sweep_point {
+                        Message::SweepPoint { settings }.print(args.json_messages, &mut stdout)?;
+                    }
+                    // keyed by world and output of the --by query
+                    let mut trials = BTreeMap::<(Option<u8>, Option<jaq_json::Val>), usize>::default();
+                    // keyed by query index, world, output of the --by query, and output
+                    let mut outputs = BTreeMap::<(usize, Option<u8>, Option<jaq_json::Val>, jaq_json::Val), usize>::default();
+                    for (_, state) in seed_states {
+                        if let SeedState::Success { extracted: Some(analysis::Extracted::Query(world_outputs)), .. } | SeedState::Failure { extracted: Some(analysis::Extracted::Query(world_outputs)), .. } = state {
+                            for analysis::WorldOutputs { world, outputs: query_outputs, by } in world_outputs {
+                                let by = if let Some(by) = by { by.into_iter().map(|value| serde_json::from_value::<jaq_json::Val>(value).map(Some)).try_collect::<_, BTreeSet<_>, _>()? } else { BTreeSet::from([None]) };
+                                for by in &by {
+                                    *trials.entry((world, by.clone())).or_default() += 1;
+                                }
+                                for (query_idx, query_outputs) in query_outputs.into_iter().enumerate() {
+                                    for value in query_outputs {
+                                        let value = serde_json::from_value::<jaq_json::Val>(value)?;
+                                        for by in &by {
+                                            *outputs.entry((query_idx, world, by.clone(), value.clone())).or_default() += 1;
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
-                    SeedState::Failure { worker, instructions: Some(instructions), rsl_instructions, .. } => {
-                        crossterm::execute!(stdout,
-                            Print(format_args!("f {instructions} {worker}\r\n")),
-                        ).at_unknown()?;
-                        if let Some(rsl_instructions) = rsl_instructions {
-                            crossterm::execute!(stdout,
-                                Print(format_args!("F {rsl_instructions} {worker}\r\n")),
-                            ).at_unknown()?;
+                    for (query_idx, (name, _)) in queries.iter().enumerate() {
+                        if let Some(name) = name {
+                            Message::QueryName { name }.print(args.json_messages, &mut stdout)?;
+                        }
+                        let mut query_outputs = outputs.iter().filter(|((iter_query_idx, ..), _)| *iter_query_idx == query_idx).collect_vec();
+                        let numbers = query_outputs.iter()
+                            .map(|((_, world, by, output), &count)| Ok::<_, Error>(query::to_json(output)?.as_f64().map(|value| ((*world, by.clone()), value, count))))
+                            .try_collect::<_, Option<Vec<_>>, _>()?;
+                        match numbers {
+                            // listing each distinct value is unreadable for continuous values
+                            // outputs are distinct and sorted by world and output of the --by query, so this counts the distinct values of the group with the most of them
+                            Some(numbers) if histogram || numbers.iter().chunk_by(|(group, _, _)| group).into_iter().map(|(_, values)| values.count()).max().is_some_and(|num_distinct| num_distinct > HISTOGRAM_DISTINCT_VALUES) => {
+                                let mut groups = BTreeMap::<_, Vec<_>>::default();
+                                for (group, value, count) in numbers {
+                                    groups.entry(group).or_default().push((value, count));
+                                }
+                                for ((world, by), mut values) in groups {
+                                    values.sort_by(|(value1, _), (value2, _)| value1.total_cmp(value2));
+                                    Message::Histogram {
+                                        summary: stats::summarize(&values).expect("empty histogram group"),
+                                        histogram: stats::histogram(&values, bins.get()).expect("empty histogram group"),
+                                        world, by,
+                                    }.print(args.json_messages, &mut stdout)?;
+                                }
+                            }
+                            _ => {
+                                query_outputs.sort_by(|((_, world1, by1, _), count1), ((_, world2, by2, _), count2)| world1.cmp(world2).then_with(|| by1.cmp(by2)).then_with(|| count2.cmp(count1)));
+                                for ((_, world, by, output), &count) in query_outputs {
+                                    let trials = trials[&(*world, by.clone())];
+                                    // a query can output the same value multiple times for the same input
+                                    let interval = stats::wilson_interval(count.min(trials), trials, precision::CONFIDENCE).expect("query output without inputs");
+                                    Message::Category { world: *world, by: by.clone(), output: output.clone(), count, trials, interval }.print(args.json_messages, &mut stdout)?;
+                                }
+                            }
                         }
                     }
                 }
-            }
-        }
-        Some(Subcommand::Categorize { query }) => {
-            let defs = jaq_core::defs().chain(jaq_std::defs()).chain(jaq_json::defs());
-            let funs = jaq_core::funs().chain(jaq_std::funs()).chain(jaq_json::funs());
-            let loader = jaq_core::load::Loader::new(defs);
-            let arena = jaq_core::load::Arena::default();
-            let program = jaq_core::load::File { code: &*query, path: () };
-            let modules = loader.load(&arena, program).map_err(|_| Error::JaqLoad)?;
-            let filter = jaq_core::Compiler::default()
-                .with_funs(funs)
-                .compile(modules).map_err(|_| Error::JaqCompile)?;
-            let ctx = jaq_core::Ctx::<jaq_core::data::JustLut<jaq_json::Val>>::new(&filter.lut, jaq_core::Vars::new([]));
-            let mut outputs = BTreeMap::<jaq_json::Val, usize>::default();
-            for state in seed_states {
-                if let SeedState::Success { spoiler_log, .. } = state {
-                    for value in filter.id.run((ctx.clone(), serde_json::from_value(spoiler_log)?)).map(jaq_core::unwrap_valr) {
-                        *outputs.entry(value.map_err(|_| Error::JaqRun)?).or_default() += 1;
+            },
+            Some(Subcommand::Failures { top, by_exception_type, normalize, ref replacements, list_seeds }) => {
+                for (sweep_point, seed_states) in group_by_sweep_point(&args.sweeps, seeds.as_ref(), seed_states) {
+                    if let Some(settings) = sweep_point {
+                        Message::SweepPoint { settings }.print(args.json_messages, &mut stdout)?;
+                    }
+                    // seed indices grouped by location or exception type (or by outcome for timeouts and out-of-memory failures) and then by message, in ascending order
+                    let mut groups = HashMap::<_, HashMap<_, Vec<SeedIdx>>>::default();
+                    for (seed_idx, state) in &seed_states {
+                        if let SeedState::Failure { timed_out, out_of_memory, error_log, .. } = state {
+                            let (location, msg) = parse_traceback(std::str::from_utf8(error_log)?);
+                            let (key, msg) = if *timed_out || *out_of_memory {
+                                // the exception message of an out-of-memory failure is just `MemoryError`, so where the allocation failed is more useful
+                                ((*timed_out, *out_of_memory, None), if *out_of_memory { location } else { msg })
+                            } else {
+                                ((false, false, Some(if by_exception_type { exception_type(msg) } else { location })), msg)
+                            };
+                            groups.entry(key).or_default().entry(normalize_failure_msg(msg, normalize, replacements)).or_default().push(*seed_idx);
+                        }
+                    }
+                    Message::FailuresHeader {
+                        failures: groups.values().map(|msgs| msgs.values().map(|seed_idxs| seed_idxs.len() as u32).sum::<u32>()).sum(),
+                        by_exception_type,
+                    }.print(args.json_messages, &mut stdout)?;
+                    for (key, msgs) in groups.into_iter().sorted_unstable_by_key(|(_, msgs)| -(msgs.values().map(Vec::len).sum::<usize>() as isize)).take(top) {
+                        let count = msgs.values().map(Vec::len).sum::<usize>();
+                        let seed_idxs = list_seeds.then(|| msgs.values().flatten().copied().sorted_unstable().collect_vec());
+                        let mut msgs = msgs.into_iter().map(|(msg, seed_idxs)| (msg, (seed_idxs[0], seed_idxs.len()))).collect_vec();
+                        msgs.sort_unstable_by_key(|&(_, (_, count))| count);
+                        let (top_msg, (seed_idx, top_count)) = msgs.pop().expect("no error messages");
+                        Message::Failure { timed_out: key.0, out_of_memory: key.1, count, top_msg, top_count, seed_idx, msgs, seed_idxs }.print(args.json_messages, &mut stdout)?;
                     }
                 }
             }
-            let mut outputs = outputs.into_iter().collect_vec();
-            outputs.sort_by(|(_, count1), (_, count2)| count2.cmp(count1));
-            for (output, count) in outputs {
-                Message::Category { output, count }.print(args.json_messages, &mut stdout)?;
+            Some(Subcommand::Find { count, .. }) => {
+                for &seed_idx in &found {
+                    Message::Found { seed_idx, seed_dir: stats_dir.join(seed_idx.to_string()) }.print(args.json_messages, &mut stdout)?;
+                }
+                if found.len() < count.get() {
+                    Message::FoundTooFew { found: found.len(), count: count.get(), num_seeds: num_seeds.get() }.print(args.json_messages, &mut stdout)?;
+                }
             }
-        }
-        Some(Subcommand::Failures) => {
-            let mut counts = HashMap::<_, HashMap<_, (SeedIdx, usize)>>::default();
-            for (seed_idx, state) in seed_states.iter().enumerate() {
-                if let SeedState::Failure { worker, error_log, .. } = state {
-                    let (location, msg) = parse_traceback(worker, seed_idx.try_into()?, std::str::from_utf8(error_log)?)?;
-                    match counts.entry(location).or_default().entry(msg) {
-                        hash_map::Entry::Occupied(mut entry) => entry.get_mut().1 += 1,
-                        hash_map::Entry::Vacant(entry) => { entry.insert((seed_idx.try_into()?, 1)); }
+            Some(Subcommand::MidosHouse { ref out_path }) => {
+                // keyed by the serialized chest appearances since JSON values aren't hashable
+                let mut counts = BTreeMap::<String, (serde_json::Value, usize)>::default();
+                for state in seed_states {
+                    if let SeedState::Success { extracted: Some(analysis::Extracted::MidosHouse(chests)), .. } = state {
+                        for appearances in chests {
+                            counts.entry(serde_json::to_string(&appearances)?).or_insert((appearances, 0)).1 += 1;
+                        }
                     }
                 }
+                fs::write_json(out_path, counts.into_values().collect_vec()).await?;
             }
-            Message::FailuresHeader { failures: counts.values().map(|msgs| msgs.values().map(|&(_, count)| count as u16).sum::<u16>()).sum() }.print(args.json_messages, &mut stdout)?;
-            for msgs in counts.into_values().sorted_unstable_by_key(|msgs| -(msgs.values().map(|&(_, count)| count).sum::<usize>() as isize)).take(10) {
-                let count = msgs.values().map(|&(_, count)| count).sum::<usize>();
-                let mut msgs = msgs.into_iter().collect_vec();
-                msgs.sort_unstable_by_key(|&(_, (_, count))| count);
-                let (top_msg, (seed_idx, top_count)) = msgs.pop().expect("no error messages");
-                Message::Failure { count, top_msg, top_count, seed_idx, msgs }.print(args.json_messages, &mut stdout)?;
+            Some(Subcommand::Bisect { failure_rate, frequency, instructions, .. }) => {
+                let criterion = if let Some(failure_rate) = failure_rate {
+                    bisect::Criterion::FailureRate(failure_rate)
+                } else if let Some(frequency) = frequency {
+                    bisect::Criterion::Query { frequency }
+                } else if let Some(instructions) = instructions {
+                    bisect::Criterion::Instructions(instructions)
+                } else {
+                    unreachable!("clap should require a criterion")
+                };
+                bisect_sample = Some(bisect::Sample::new(&criterion, seed_states));
             }
-        }
-        Some(Subcommand::MidosHouse { out_path }) => {
-            let mut counts = HashMap::<_, usize>::default();
-            for state in seed_states {
-                if let SeedState::Success { spoiler_log, .. } = state {
-                    for appearances in serde_json::from_value::<SpoilerLog>(spoiler_log)?.midos_house_chests() {
-                        *counts.entry(appearances).or_default() += 1;
+            Some(Subcommand::BenchHistory { .. }) => unreachable!("handled in main"),
+            Some(Subcommand::Repro { .. }) => unreachable!("handled before rolling seeds"),
+            Some(Subcommand::WorldCounts { .. }) => {
+                #[derive(Default)]
+                struct Group {
+                    num_successes: u32,
+                    num_failures: u32,
+                    benched_successes: u32,
+                    benched_failures: u32,
+                    instructions_success: u64,
+                    instructions_failure: u64,
+                }
+
+                let mut groups = BTreeMap::<u8, Group>::default();
+                for state in &seed_states {
+                    match *state {
+                        SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } => unreachable!(),
+                        SeedState::Cancelled | SeedState::InfraFailure | SeedState::Success { world_count: None, .. } | SeedState::Failure { world_count: None, .. } => {}
+                        SeedState::Success { instructions, world_count: Some(world_count), .. } => {
+                            let group = groups.entry(world_count).or_default();
+                            group.num_successes += 1;
+                            if let Some(instructions) = instructions {
+                                group.benched_successes += 1;
+                                group.instructions_success += instructions;
+                            }
+                        }
+                        SeedState::Failure { instructions, world_count: Some(world_count), .. } => {
+                            let group = groups.entry(world_count).or_default();
+                            group.num_failures += 1;
+                            if let Some(instructions) = instructions {
+                                group.benched_failures += 1;
+                                group.instructions_failure += instructions;
+                            }
+                        }
                     }
                 }
+                let mut instructions_points = Vec::default();
+                let mut failure_rate_points = Vec::default();
+                for (&world_count, group) in &groups {
+                    let average_instructions_success = group.instructions_success.checked_div(group.benched_successes.into());
+                    let average_instructions_failure = group.instructions_failure.checked_div(group.benched_failures.into());
+                    if let Some(average_instructions_success) = average_instructions_success {
+                        instructions_points.push((f64::from(world_count), average_instructions_success as f64));
+                    }
+                    failure_rate_points.push((f64::from(world_count), f64::from(group.num_failures) / f64::from(group.num_successes + group.num_failures)));
+                    Message::WorldCount {
+                        num_successes: group.num_successes,
+                        num_failures: group.num_failures,
+                        world_count, average_instructions_success, average_instructions_failure,
+                    }.print(args.json_messages, &mut stdout)?;
+                }
+                Message::WorldCountsFit {
+                    instructions: stats::power_fit(&instructions_points),
+                    failure_rate: stats::linear_fit(&failure_rate_points),
+                }.print(args.json_messages, &mut stdout)?;
             }
-            let mut counts = counts.into_iter().collect_vec();
-            counts.sort_unstable();
-            fs::write_json(out_path, counts).await?;
         }
     }
     let worker_errors = workers.into_iter()
@@ -1259,35 +2590,66 @@ async fn cli(label: Option<&'static str>, mut args: Args) -> Result<bool, Error>
         }
         return Err(Error::Worker { worker_errors, cancelled: cancelled_by_user })
     }
-    Ok(cancelled_by_user)
+    if !failed_verdicts.is_empty() {
+        return Err(Error::BenchGate(failed_verdicts))
+    }
+    Ok(Outcome { cancelled: cancelled_by_user, bisect_sample })
 }
 
 #[wheel::main(custom_exit)]
 async fn main(args: Args) -> Result<(), Error> {
     let _ = rustls::crypto::ring::default_provider().install_default();
+    if let Some(Subcommand::BenchHistory { threshold }) = args.subcommand {
+        return bench_history(args, threshold).await
+    }
     if !args.json_messages {
         enable_raw_mode().at_unknown()?;
     }
     let res = 'res: {
-        if args.suite {
+        if let Some(ref suite) = args.suite {
             let mut first_network_error = None;
+            let mut first_gate_failure = None;
             let mut any_cancelled = false;
-            for (label, args) in [
-                ("Default / Beginner", args.clone()),
-                ("Tournament", Args { preset: Some(format!("tournament")), ..args.clone() }),
-                ("Multiworld", Args { preset: Some(format!("mw")), ..args.clone() }),
-                ("Hell Mode", Args { preset: Some(format!("hell")), ..args.clone() }),
-                ("Random Settings", if args.github_user == "fenhl" {
-                    Args { rsl: true, branch: args.branch.is_some_and(|branch| branch == "riir").then(|| format!("riir2")), preset: Some(format!("fenhl")), ..args }
-                } else {
-                    Args { rsl: true, github_user: format!("fenhl"), branch: Some(format!("dev-mvp")), ..args }
-                }), //TODO check to make sure plando-random-settings branch is up to date with matthewkirby:master and the randomizer commit specified in rslversion.py is equal to the specified randomizer commit
-            ] {
-                match cli(Some(label), args).await {
-                    Ok(cancelled) => if cancelled {
+            let suite = match Suite::load(suite).await {
+                Ok(suite) => suite,
+                Err(e) => break 'res Err(e.into()),
+            };
+            let runs = suite.entries.into_iter().map(|entry| {
+                let mut json_settings = entry.json_settings;
+                json_settings.extend(args.json_settings.clone()); // settings given on the command line take precedence
+                let uses_own_repo = entry.github_user.is_some() || entry.repo.is_some() || entry.branch.is_some();
+                (entry.label, Args {
+                    rsl: entry.rsl,
+                    github_user: if let Some(github_user) = entry.github_user {
+                        github_user
+                    } else if entry.rsl {
+                        format!("matthewkirby")
+                    } else if uses_own_repo {
+                        format!("OoTRandomizer")
+                    } else {
+                        args.github_user.clone()
+                    },
+                    repo: if uses_own_repo || entry.rsl { entry.repo } else { args.repo.clone() },
+                    branch: if uses_own_repo || entry.rsl { entry.branch } else { args.branch.clone() },
+                    rev: if uses_own_repo || entry.rsl { None } else { args.rev },
+                    ab_rev: if uses_own_repo || entry.rsl { None } else { args.ab_rev },
+                    baseline_rev: if uses_own_repo || entry.rsl { None } else { args.baseline_rev },
+                    preset: entry.preset,
+                    settings: entry.settings,
+                    draft: entry.draft,
+                    // --num-successes and --seeds override the suite's sample sizes
+                    num_seeds: if args.num_successes.is_some() || args.seeds.is_some() || args.seeds_file.is_some() { None } else { args.num_seeds.or(entry.num_seeds) },
+                    json_settings,
+                    ..args.clone()
+                })
+            }).collect_vec();
+            for (label, args) in runs {
+                match cli(Some(&label), args).await {
+                    Ok(Outcome { cancelled, .. }) => if cancelled {
                         any_cancelled = true;
                         break
                     },
+                    Err(e @ Error::BenchGate(_)) => { first_gate_failure.get_or_insert(e); }
                     Err(e) => if e.is_network_error() {
                         if let Error::Worker { cancelled: true, .. } = e {
                             any_cancelled = true;
@@ -1299,13 +2661,15 @@ async fn main(args: Args) -> Result<(), Error> {
                     },
                 }
             }
-            if let Some(e) = first_network_error {
+            if let Some(e) = first_network_error.or(first_gate_failure) {
                 Err(e)
             } else {
                 Ok(any_cancelled)
             }
+        } else if let Some(Subcommand::Bisect { .. }) = args.subcommand {
+            bisect(args).await
         } else {
-            cli(None, args).await
+            cli(None, args).await.map(|outcome| outcome.cancelled)
         }
     };
     disable_raw_mode().at_unknown()?;
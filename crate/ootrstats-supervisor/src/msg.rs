@@ -39,54 +39,59 @@ use {
         IoResultExt as _,
         IsNetworkError as _,
     },
-    ootrstats::SeedIdx,
+    ootrstats::{
+        SeedIdx,
+        WorldCounts,
+    },
     crate::{
+        BenchVerdict,
         Error,
+        SeedList,
         SeedState,
+        history,
+        nth_seed,
+        query,
+        stats::{
+            Bin,
+            Histogram,
+            Interval,
+            LinearFit,
+            PowerFit,
+            Summary,
+        },
         worker,
     },
 };
 
-fn serialize_jaq_json<S: Serializer>(v: &jaq_json::Val, serializer: S) -> Result<S::Ok, S::Error> {
-    #[derive(Debug, thiserror::Error)]
-    enum JaqToSerdeError {
-        #[error(transparent)] Json(#[from] serde_json_inner::Error),
-        #[error(transparent)] Utf8(#[from] std::str::Utf8Error),
-        #[error("got a jaq object with a non-string key")]
-        KeyType,
-    }
+/// The length of the bar for the largest bin of a histogram, in characters.
+const HISTOGRAM_WIDTH: usize = 40;
 
-    fn jaq_to_serde(v: &jaq_json::Val) -> Result<serde_json::Value, JaqToSerdeError> {
-        Ok(match v {
-            jaq_json::Val::Null => serde_json::Value::Null,
-            jaq_json::Val::Bool(b) => (*b).into(),
-            jaq_json::Val::Num(jaq_json::Num::Int(n)) => (*n).into(),
-            jaq_json::Val::Num(jaq_json::Num::Float(f)) => (*f).into(),
-            jaq_json::Val::Num(n) => serde_json::Value::Number(n.to_string().parse()?),
-            jaq_json::Val::BStr(s) | jaq_json::Val::TStr(s) => std::str::from_utf8(s)?.into(),
-            jaq_json::Val::Arr(a) => a.iter().map(jaq_to_serde).try_collect()?,
-            jaq_json::Val::Obj(o) => o.iter().map(|(k, v)| Ok((match k {
-                jaq_json::Val::BStr(s) | jaq_json::Val::TStr(s) => std::str::from_utf8(s)?.to_owned(),
-                _ => return Err(JaqToSerdeError::KeyType),
-            }, jaq_to_serde(v)?))).try_collect()?,
-        })
-    }
+fn serialize_jaq_json<S: Serializer>(v: &jaq_json::Val, serializer: S) -> Result<S::Ok, S::Error> {
+    query::to_json(v).map_err(S::Error::custom)?.serialize(serializer)
+}
 
-    jaq_to_serde(v).map_err(S::Error::custom)?.serialize(serializer)
+fn serialize_opt_jaq_json<S: Serializer>(v: &Option<jaq_json::Val>, serializer: S) -> Result<S::Ok, S::Error> {
+    v.as_ref().map(query::to_json).transpose().map_err(S::Error::custom)?.serialize(serializer)
 }
 
 #[derive(Serialize)]
 pub(crate) enum Message<'a> {
-    Preparing(Option<&'static str>),
+    Preparing(Option<&'a str>),
     Status {
-        label: Option<&'static str>,
-        available_parallelism: NonZero<u16>,
-        completed_readers: u16,
+        label: Option<&'a str>,
+        available_parallelism: NonZero<SeedIdx>,
+        completed_readers: SeedIdx,
         retry_failures: bool,
-        world_counts: bool,
+        #[serde(skip)]
+        world_counts: Option<&'a WorldCounts>,
+        /// present if the `--seeds` or `--seeds-file` option was used, in which case the seed states are only for the listed seeds.
+        #[serde(skip)]
+        seeds: Option<&'a SeedList>,
         seed_states: &'a [SeedState],
-        allowed_workers: &'a HashMap<SeedIdx, NEVec<Arc<str>>>,
+        allowed_workers: &'a HashMap<usize, NEVec<Arc<str>>>,
         retried_failures: &'a [u32],
+        /// for each seed, the workers on which it had an infrastructure failure.
+        infra_failures: &'a [Vec<Arc<str>>],
         #[serde(skip)]
         start: Instant,
         #[serde(skip)]
@@ -94,35 +99,149 @@ pub(crate) enum Message<'a> {
         workers: &'a [worker::State],
     },
     Done {
-        label: Option<&'static str>,
+        label: Option<&'a str>,
         num_workers: u16,
         stats_dir: PathBuf,
+        /// present if the `--ab-rev` option was used.
+        ab_stats_dir: Option<PathBuf>,
+    },
+    /// The number of infrastructure failures on each worker, displayed if there were any. These seeds were rolled again (unless given up on, see `InfraFailureSeeds`) and aren't counted as failures.
+    InfraFailures(Vec<(Arc<str>, usize)>),
+    /// Seeds which were given up on after repeated infrastructure failures, displayed before a subcommand's output if there were any. They aren't included in that output.
+    InfraFailureSeeds {
+        seed_idxs: Vec<SeedIdx>,
+    },
+    /// The precision reached with the `--until-ci` option.
+    Precision {
+        /// present if the `--ab-rev` option was used.
+        rev: Option<String>,
+        num_seeds: usize,
+        /// the largest half-width of the confidence intervals of the estimated frequencies, or `None` if no seeds were rolled.
+        half_width: Option<f64>,
+        target: f64,
+        confidence: f64,
+    },
+    /// Precedes the output of a subcommand for the seeds of this revision if the `--ab-rev` option was used.
+    AbRev {
+        rev: String,
+    },
+    /// Precedes the output of the `bench`, `categorize`, or `failures` subcommand for the seeds at this sweep point.
+    SweepPoint {
+        settings: serde_json::Map<String, serde_json::Value>,
     },
     InstructionsNoSuccesses,
     Instructions {
         rsl: bool,
-        num_successes: u16,
-        num_failures: u16,
+        num_successes: u32,
+        num_failures: u32,
         success_rate: f64,
         average_instructions_success: u64,
         average_instructions_failure: u64,
         average_failure_count: f64,
         average_instructions: f64,
     },
+    /// The result of the `--max-regression` and `--max-failure-rate` checks.
+    BenchVerdict(&'a BenchVerdict),
+    /// Precedes the output of the `categorize` subcommand for a query from a `--query-file`.
+    QueryName {
+        name: &'a str,
+    },
     Category {
+        /// present if the query was run separately for each world.
+        world: Option<u8>,
+        /// present if the `--by` option was used.
+        #[serde(serialize_with = "serialize_opt_jaq_json")]
+        by: Option<jaq_json::Val>,
         count: usize,
+        /// the number of query inputs (seeds, or worlds with `--per-world`) with the same world and output of the `--by` query.
+        trials: usize,
+        /// the fraction of inputs with this output, at the confidence level of the `--until-ci` option.
+        interval: Interval,
         #[serde(serialize_with = "serialize_jaq_json")]
         output: jaq_json::Val,
     },
+    /// Numeric outputs of a `categorize` query.
+    Histogram {
+        /// present if the query was run separately for each world.
+        world: Option<u8>,
+        /// present if the `--by` option was used.
+        #[serde(serialize_with = "serialize_opt_jaq_json")]
+        by: Option<jaq_json::Val>,
+        summary: Summary,
+        histogram: Histogram,
+    },
     FailuresHeader {
-        failures: u16,
+        failures: u32,
+        by_exception_type: bool,
     },
     Failure {
+        /// whether this group consists of the seeds which exceeded the `--seed-timeout`. If so, the messages are the last lines of output before the randomizer was killed.
+        timed_out: bool,
+        /// whether this group consists of the seeds which exceeded the worker's `memory_limit`. If so, the messages are the locations where an allocation failed.
+        out_of_memory: bool,
         count: usize,
-        top_msg: &'a str,
+        top_msg: String,
         top_count: usize,
         seed_idx: SeedIdx,
-        msgs: Vec<(&'a str, (SeedIdx, usize))>,
+        msgs: Vec<(String, (SeedIdx, usize))>,
+        /// all seeds in this group, present if the `--list-seeds` option was used.
+        seed_idxs: Option<Vec<SeedIdx>>,
+    },
+    /// A seed that matches the query given to the `find` subcommand.
+    Found {
+        seed_idx: SeedIdx,
+        seed_dir: PathBuf,
+    },
+    /// The `find` subcommand ran out of seeds before finding the requested number of matches.
+    FoundTooFew {
+        found: usize,
+        count: usize,
+        num_seeds: SeedIdx,
+    },
+    /// A progress update from the `repro` subcommand.
+    ReproProgress(&'a str),
+    /// A seed rolled by the `repro` subcommand.
+    Repro {
+        seed_idx: SeedIdx,
+        success: bool,
+        out_dir: PathBuf,
+    },
+    BenchHistoryGroup {
+        github_user: &'a str,
+        repo: &'a str,
+        label: Option<&'a str>,
+    },
+    BenchHistoryEntry {
+        entry: history::Entry,
+        /// relative change in average instructions compared to the previous revision.
+        change: Option<f64>,
+        regression: bool,
+    },
+    BisectStep {
+        rev: String,
+        num_seeds: SeedIdx,
+        interval: Option<Interval>,
+        /// whether the interval is for a fraction of seeds rather than an instruction count.
+        proportion: bool,
+        threshold: f64,
+        /// `Some(true)` if the revision is bad, `Some(false)` if it's good, `None` if more seeds are needed.
+        verdict: Option<bool>,
+    },
+    BisectResult {
+        rev: String,
+        /// false if some revisions were classified based on the point estimate because the result wasn't significant with the maximum number of seeds.
+        significant: bool,
+    },
+    WorldCount {
+        world_count: u8,
+        num_successes: u32,
+        num_failures: u32,
+        average_instructions_success: Option<u64>,
+        average_instructions_failure: Option<u64>,
+    },
+    WorldCountsFit {
+        instructions: Option<PowerFit>,
+        failure_rate: Option<LinearFit>,
     },
 }
 
@@ -142,10 +261,10 @@ impl Message<'_> {
                 Self::Preparing(Some(label)) => crossterm::execute!(writer,
                     Print(format_args!("{label}: preparing...")),
                 ).at_unknown()?,
-                Self::Status { label, available_parallelism, completed_readers, retry_failures, world_counts, seed_states, allowed_workers, retried_failures, start, start_local, workers } => {
+                Self::Status { label, available_parallelism, completed_readers, retry_failures, world_counts, seeds, seed_states, allowed_workers, retried_failures, infra_failures, start, start_local, workers } => {
                     let all_assigned = seed_states.iter()
                         .enumerate()
-                        .all(|(seed_idx, seed_state)| matches!(seed_state, SeedState::Unchecked) || allowed_workers.get(&(seed_idx as SeedIdx)).is_some_and(|assigned_workers| assigned_workers.len() == NonZero::<usize>::MIN));
+                        .all(|(seed_idx, seed_state)| matches!(seed_state, SeedState::Unchecked) || allowed_workers.get(&seed_idx).is_some_and(|assigned_workers| assigned_workers.len() == NonZero::<usize>::MIN));
                     for worker in workers {
                         if let Some(ref e) = worker.error {
                             let kind = if e.is_network_error() { "network error" } else { "error" };
@@ -201,10 +320,10 @@ impl Message<'_> {
                             }
                         } else {
                             let mut running = Vec::default();
-                            let mut completed = 0u16;
-                            let mut total_completed = 0u16;
-                            let mut failures = 0u16;
-                            let mut assigned = 0u16;
+                            let mut completed = 0u32;
+                            let mut total_completed = 0u32;
+                            let mut failures = 0u32;
+                            let mut assigned = 0u32;
                             for (seed_idx, state) in seed_states.into_iter().enumerate() {
                                 match state {
                                     SeedState::Success { worker: name, .. } => {
@@ -222,9 +341,10 @@ impl Message<'_> {
                                     | SeedState::Unchecked
                                     | SeedState::Pending
                                     | SeedState::Cancelled
+                                    | SeedState::InfraFailure
                                         => {}
                                 }
-                                if let Some(assigned_workers) = allowed_workers.get(&(seed_idx as SeedIdx)) {
+                                if let Some(assigned_workers) = allowed_workers.get(&seed_idx) {
                                     if assigned_workers.len() == NonZero::<usize>::MIN {
                                         if *assigned_workers.first() == worker.name { assigned += 1 }
                                     }
@@ -250,8 +370,8 @@ impl Message<'_> {
                                 if running.is_empty() {
                                     Cow::Borrowed("0 running")
                                 } else {
-                                    if world_counts {
-                                        Cow::Owned(format!("{} running: {}", running.len(), running.into_iter().map(|seed_idx| seed_idx + 1).format(", ")))
+                                    if let Some(world_counts) = world_counts {
+                                        Cow::Owned(format!("{} running: {}", running.len(), running.into_iter().filter_map(|seed_idx| world_counts.world_count(nth_seed(seeds, seed_idx))).format(", ")))
                                     } else {
                                         Cow::Owned(format!("{} running", running.len()))
                                     }
@@ -264,11 +384,11 @@ impl Message<'_> {
                                     if all_assigned {
                                         format!("/{assigned} rolled")
                                     } else if total_completed > 0 {
-                                        format!(" rolled ({}%)", 100 * u32::from(completed) / u32::from(total_completed))
+                                        format!(" rolled ({}%)", 100 * u64::from(completed) / u64::from(total_completed))
                                     } else {
                                         format!(" rolled")
                                     },
-                                    if failures > 0 { format!(", failure rate {}%", 100 * u32::from(failures) / u32::from(completed)) } else { String::default() },
+                                    if failures > 0 { format!(", failure rate {}%", 100 * u64::from(failures) / u64::from(completed)) } else { String::default() },
                                 )),
                                 Clear(ClearType::UntilNewLine),
                             ).at_unknown()?;
@@ -279,15 +399,27 @@ impl Message<'_> {
                         MoveToColumn(0),
                         Print(if completed_readers == available_parallelism.get() {
                             // list of pending seeds fully initialized
-                            let mut num_successes = 0u16;
-                            let mut num_failures = 0u16;
-                            let mut started = 0u16;
-                            let mut total = 0u16;
-                            let mut completed = 0u16;
+                            let mut num_successes = 0u32;
+                            let mut num_failures = 0u32;
+                            let mut num_timeouts = 0u32;
+                            let mut num_out_of_memory = 0u32;
+                            let mut started = 0u32;
+                            let mut total = 0u32;
+                            let mut completed = 0u32;
                             let mut last_completed = None;
-                            let mut skipped = 0u16;
-                            let mut continuous = 0;
+                            let mut skipped = 0u32;
+                            let mut num_infra_given_up = 0u32;
+                            // smallest world count with seeds that haven't been rolled
+                            let mut min_incomplete_world_count = None::<u8>;
                             for (seed_idx, state) in seed_states.into_iter().enumerate() {
+                                if_chain! {
+                                    if !matches!(state, SeedState::Success { .. } | SeedState::Failure { .. });
+                                    if let Some(world_count) = world_counts.and_then(|world_counts| world_counts.world_count(nth_seed(seeds, seed_idx)));
+                                    then {
+                                        let min_incomplete_world_count = min_incomplete_world_count.get_or_insert(world_count);
+                                        *min_incomplete_world_count = world_count.min(*min_incomplete_world_count);
+                                    }
+                                }
                                 match *state {
                                     SeedState::Unchecked => unreachable!(),
                                     SeedState::Pending => total += 1,
@@ -296,13 +428,11 @@ impl Message<'_> {
                                         started += 1;
                                     }
                                     SeedState::Cancelled => {}
+                                    SeedState::InfraFailure => num_infra_given_up += 1,
                                     SeedState::Success { completed_at, .. } => {
                                         total += 1;
                                         started += 1;
                                         num_successes += 1;
-                                        if continuous == seed_idx {
-                                            continuous += 1;
-                                        }
                                         if let Some(completed_at) = completed_at {
                                             completed += 1;
                                             let last_completed = last_completed.get_or_insert(completed_at);
@@ -311,12 +441,15 @@ impl Message<'_> {
                                             skipped += 1;
                                         }
                                     }
-                                    SeedState::Failure { completed_at, .. } => {
+                                    SeedState::Failure { completed_at, timed_out, out_of_memory, .. } => {
                                         total += 1;
                                         started += 1;
-                                        num_failures += 1;
-                                        if continuous == seed_idx {
-                                            continuous += 1;
+                                        if timed_out {
+                                            num_timeouts += 1;
+                                        } else if out_of_memory {
+                                            num_out_of_memory += 1;
+                                        } else {
+                                            num_failures += 1;
                                         }
                                         if let Some(completed_at) = completed_at {
                                             completed += 1;
@@ -328,14 +461,16 @@ impl Message<'_> {
                                     }
                                 }
                             }
-                            let rolled = num_successes + num_failures;
+                            let rolled = num_successes + num_failures + num_timeouts + num_out_of_memory;
                             format!(
-                                "{}{started}/{total} seeds started, {rolled} rolled{}{}, ETA {}",
+                                "{}{started}/{total} seeds started, {rolled} rolled{}{}{}{}{}, ETA {}",
                                 if let Some(label) = label { format!("{label}: ") } else { String::default() },
-                                if world_counts {
-                                    format!(" (continuous up to {continuous} worlds)")
-                                } else {
+                                if world_counts.is_none() {
                                     String::default()
+                                } else if let Some(min_incomplete_world_count) = min_incomplete_world_count {
+                                    format!(" (continuous up to {} worlds)", min_incomplete_world_count - 1)
+                                } else {
+                                    format!(" (all world counts done)")
                                 },
                                 if retry_failures {
                                     let num_failures = retried_failures.iter().sum::<u32>();
@@ -347,22 +482,48 @@ impl Message<'_> {
                                     format!(
                                         ", {num_failures} failure{} ({}%)",
                                         if num_failures == 1 { "" } else { "s" },
-                                        if num_successes > 0 || num_failures > 0 { 100 * u32::from(num_failures) / u32::from(num_successes + num_failures) } else { 100 },
+                                        if rolled > 0 { 100 * u64::from(num_failures) / u64::from(rolled) } else { 100 },
+                                    )
+                                },
+                                if num_timeouts > 0 {
+                                    format!(
+                                        ", {num_timeouts} timeout{} ({}%)",
+                                        if num_timeouts == 1 { "" } else { "s" },
+                                        100 * u64::from(num_timeouts) / u64::from(rolled),
                                     )
+                                } else {
+                                    String::default()
+                                },
+                                if num_out_of_memory > 0 {
+                                    format!(", {num_out_of_memory} out of memory ({}%)", 100 * u64::from(num_out_of_memory) / u64::from(rolled))
+                                } else {
+                                    String::default()
+                                },
+                                match infra_failures.iter().map(Vec::len).sum::<usize>() {
+                                    0 => String::default(),
+                                    num_infra_failures => format!(
+                                        ", {num_infra_failures} infrastructure failure{}{}",
+                                        if num_infra_failures == 1 { "" } else { "s" },
+                                        match num_infra_given_up {
+                                            0 => String::default(),
+                                            1 => format!(" (1 seed given up)"),
+                                            _ => format!(" ({num_infra_given_up} seeds given up)"),
+                                        },
+                                    ),
                                 },
                                 if_chain! {
                                     if let Some(estimated_duration) = if all_assigned {
                                         workers.iter()
                                             .map(|worker| {
-                                                let mut total = 0u16;
-                                                let mut completed = 0u16;
+                                                let mut total = 0u32;
+                                                let mut completed = 0u32;
                                                 let mut last_completed = None;
                                                 for (seed_idx, state) in seed_states.iter().enumerate() {
-                                                    if allowed_workers.get(&(seed_idx as SeedIdx)).is_none_or(|allowed_workers| allowed_workers.contains(&worker.name)) {
+                                                    if allowed_workers.get(&seed_idx).is_none_or(|allowed_workers| allowed_workers.contains(&worker.name)) {
                                                         match *state {
                                                             SeedState::Unchecked => unreachable!(),
                                                             SeedState::Pending | SeedState::Rolling { .. } => total += 1,
-                                                            SeedState::Cancelled | SeedState::Success { completed_at: None, .. } | SeedState::Failure { completed_at: None, .. } => {}
+                                                            SeedState::Cancelled | SeedState::InfraFailure | SeedState::Success { completed_at: None, .. } | SeedState::Failure { completed_at: None, .. } => {}
                                                             SeedState::Success { completed_at: Some(completed_at), .. } | SeedState::Failure { completed_at: Some(completed_at), .. } => {
                                                                 total += 1;
                                                                 completed += 1;
@@ -375,13 +536,13 @@ impl Message<'_> {
                                                 if total == 0 {
                                                     Some(Duration::default())
                                                 } else {
-                                                    last_completed.map(|last_completed| (last_completed - start) * u32::from(total) / u32::from(completed))
+                                                    last_completed.map(|last_completed| (last_completed - start) * total / completed)
                                                 }
                                             })
                                             .collect::<Option<Vec<_>>>()
                                             .and_then(|worker_durations| worker_durations.into_iter().max())
                                     } else {
-                                        last_completed.map(|last_completed| (last_completed - start) * u32::from(total - skipped) / u32::from(completed))
+                                        last_completed.map(|last_completed| (last_completed - start) * (total - skipped) / completed)
                                     };
                                     if let Ok(estimated_duration) = TimeDelta::from_std(estimated_duration);
                                     then {
@@ -392,16 +553,16 @@ impl Message<'_> {
                                 },
                             )
                         } else {
-                            let mut rolled = 0u16;
-                            let mut started = 0u16;
-                            let mut pending = 0u16;
-                            let mut unchecked = 0u16;
+                            let mut rolled = 0u32;
+                            let mut started = 0u32;
+                            let mut pending = 0u32;
+                            let mut unchecked = 0u32;
                             for state in seed_states {
                                 match state {
                                     SeedState::Unchecked => unchecked += 1,
                                     SeedState::Pending => pending += 1,
                                     SeedState::Rolling { .. } => started += 1,
-                                    SeedState::Cancelled => {}
+                                    SeedState::Cancelled | SeedState::InfraFailure => {}
                                     SeedState::Success { .. } | SeedState::Failure { .. } => rolled += 1,
                                 }
                             }
@@ -426,7 +587,7 @@ impl Message<'_> {
                         Clear(ClearType::UntilNewLine),
                     ).at_unknown()?;
                 }
-                Self::Done { label, num_workers, stats_dir } => {
+                Self::Done { label, num_workers, stats_dir, ab_stats_dir } => {
                     for _ in 0..num_workers {
                         crossterm::execute!(writer,
                             Print("\r\n"),
@@ -436,10 +597,36 @@ impl Message<'_> {
                     crossterm::execute!(writer,
                         MoveUp(num_workers),
                         Print(format_args!("{}stats saved to {}", if let Some(label) = label { format!("{label}: ") } else { String::default() }, stats_dir.display())),
+                        Print(if let Some(ab_stats_dir) = ab_stats_dir { format!(" and {}", ab_stats_dir.display()) } else { String::default() }),
                         Clear(ClearType::UntilNewLine),
                         Print("\r\n"),
                     ).at_unknown()?;
                 }
+                Self::InfraFailures(counts) => crossterm::execute!(writer,
+                    Print(format_args!("infrastructure failures (not counted as failures): {}\r\n", counts.into_iter().map(|(worker, count)| format!("{count} on {worker}")).format(", "))),
+                ).at_unknown()?,
+                Self::InfraFailureSeeds { seed_idxs } => crossterm::execute!(writer,
+                    Print(format_args!(
+                        "{} given up after repeated infrastructure failures and not included below: {}\r\n",
+                        if seed_idxs.len() == 1 { format!("1 seed") } else { format!("{} seeds", seed_idxs.len()) },
+                        seed_idxs.iter().format(", "),
+                    )),
+                ).at_unknown()?,
+                Self::Precision { rev, num_seeds, half_width, target, confidence } => crossterm::execute!(writer,
+                    Print(format_args!(
+                        "{}precision after {num_seeds} seeds: {} at {:.0}% confidence (target ±{:.02}%)\r\n",
+                        if let Some(rev) = rev { format!("revision {rev}: ") } else { String::default() },
+                        if let Some(half_width) = half_width { format!("±{:.02}%", half_width * 100.0) } else { format!("unknown") },
+                        confidence * 100.0,
+                        target * 100.0,
+                    )),
+                ).at_unknown()?,
+                Self::AbRev { rev } => crossterm::execute!(writer,
+                    Print(format_args!("revision {rev}:\r\n")),
+                ).at_unknown()?,
+                Self::SweepPoint { settings } => crossterm::execute!(writer,
+                    Print(format_args!("{}:\r\n", settings.iter().map(|(setting, value)| format!("{setting} = {value}")).format(", "))),
+                ).at_unknown()?,
                 Self::InstructionsNoSuccesses => crossterm::execute!(writer,
                     Print("No successful seeds, so average instruction count is infinite\r\n"),
                 ).at_unknown()?,
@@ -449,21 +636,168 @@ impl Message<'_> {
                     Print(format_args!("average instructions (failure){}: {}\r\n", if rsl { " (RSL script)" } else { "" }, if num_failures == 0 { format!("N/A") } else { format!("{average_instructions_failure} ({average_instructions_failure:.3e})") })),
                     Print(format_args!("average total instructions until success{}: {average_instructions} ({average_instructions:.3e})\r\n", if rsl { " (RSL script)" } else { "" })),
                 ).at_unknown()?,
-                Self::Category { count, output } => crossterm::execute!(writer,
-                    Print(format_args!("{count}x: {output}\r\n")),
+                Self::BenchVerdict(BenchVerdict { passed, baseline_rev, confidence, regression, max_regression, failure_rate_increase, max_failure_rate, .. }) => {
+                    let format_interval = |interval: Option<Interval>, unit: &str| if let Some(Interval { estimate, lower, upper }) = interval {
+                        format!("{:+.02}{unit} ({:+.02}{unit} to {:+.02}{unit})", estimate * 100.0, lower * 100.0, upper * 100.0)
+                    } else {
+                        format!("no data")
+                    };
+                    crossterm::execute!(writer,
+                        Print(format_args!(
+                            "benchmark gate {} (compared to {} at {}% confidence)\r\n",
+                            if *passed { "passed" } else { "FAILED" },
+                            baseline_rev.as_deref().unwrap_or("baseline"),
+                            confidence * 100.0,
+                        )),
+                    ).at_unknown()?;
+                    if let Some(max_regression) = max_regression {
+                        crossterm::execute!(writer,
+                            Print(format_args!("change in average total instructions until success: {}, limit {:+.02}%\r\n", format_interval(*regression, "%"), max_regression * 100.0)),
+                        ).at_unknown()?;
+                    }
+                    if let Some(max_failure_rate) = max_failure_rate {
+                        crossterm::execute!(writer,
+                            Print(format_args!("change in failure rate: {}, limit {:+.02} percentage points\r\n", format_interval(*failure_rate_increase, " percentage points"), max_failure_rate * 100.0)),
+                        ).at_unknown()?;
+                    }
+                }
+                Self::QueryName { name } => crossterm::execute!(writer,
+                    Print(format_args!("{name}:\r\n")),
                 ).at_unknown()?,
-                Self::FailuresHeader { failures } => crossterm::execute!(writer,
-                    Print(format_args!("{failures} failures, top failure reasons by last line:\r\n")),
+                Self::Category { world, by, count, trials, interval: Interval { estimate, lower, upper }, output } => crossterm::execute!(writer,
+                    Print(format_args!(
+                        "{}{}{count}/{trials} ({:.02}%, {:.02}%–{:.02}%): {output}\r\n",
+                        if let Some(world) = world { format!("world {world}: ") } else { String::default() },
+                        if let Some(by) = by { format!("{by}: ") } else { String::default() },
+                        estimate * 100.0,
+                        lower * 100.0,
+                        upper * 100.0,
+                    )),
                 ).at_unknown()?,
-                Self::Failure { count, top_msg, top_count, seed_idx, msgs } => if msgs.is_empty() {
+                Self::Histogram { world, by, summary: Summary { count, mean, min, p5, p25, median, p75, p95, max }, histogram: Histogram { integral, bins } } => {
+                    let prefix = format!(
+                        "{}{}",
+                        if let Some(world) = world { format!("world {world}: ") } else { String::default() },
+                        if let Some(by) = by { format!("{by}: ") } else { String::default() },
+                    );
                     crossterm::execute!(writer,
-                        Print(format_args!("{count}x: {top_msg} (e.g. seed {seed_idx})\r\n")),
+                        Print(format_args!("{prefix}{count} values, mean {mean:.02}, median {median}, 5th/25th/75th/95th percentiles {p5}/{p25}/{p75}/{p95}, range {min}–{max}\r\n")),
                     ).at_unknown()?;
-                } else {
+                    let max_count = bins.iter().map(|bin| bin.count).max().unwrap_or_default().max(1);
+                    for Bin { lower, upper, count } in bins {
+                        crossterm::execute!(writer,
+                            Print(format_args!(
+                                "{prefix}  {}: {count} {}\r\n",
+                                if !integral {
+                                    format!("{lower:.03}–{upper:.03}")
+                                } else if upper - lower == 1.0 {
+                                    format!("{lower}")
+                                } else {
+                                    format!("{lower}–{}", upper - 1.0)
+                                },
+                                "█".repeat(count * HISTOGRAM_WIDTH / max_count),
+                            )),
+                        ).at_unknown()?;
+                    }
+                }
+                Self::FailuresHeader { failures, by_exception_type } => crossterm::execute!(writer,
+                    Print(format_args!("{failures} failures, top failure reasons by {}:\r\n", if by_exception_type { "exception type" } else { "last line" })),
+                ).at_unknown()?,
+                Self::Failure { timed_out, out_of_memory, count, top_msg, top_count, seed_idx, msgs, seed_idxs } => {
+                    let prefix = if timed_out { "timed out, last output: " } else if out_of_memory { "out of memory, at: " } else { "" };
+                    if msgs.is_empty() {
+                        crossterm::execute!(writer,
+                            Print(format_args!("{count}x: {prefix}{top_msg} (e.g. seed {seed_idx})\r\n")),
+                        ).at_unknown()?;
+                    } else {
+                        crossterm::execute!(writer,
+                            Print(format_args!("{count}x: {prefix}{top_msg} ({top_count}x, e.g. seed {seed_idx}, and {} other variants)\r\n", msgs.len())),
+                        ).at_unknown()?;
+                    }
+                    if let Some(seed_idxs) = seed_idxs {
+                        crossterm::execute!(writer,
+                            Print(format_args!("  seeds: {}\r\n", seed_idxs.iter().format(", "))),
+                        ).at_unknown()?;
+                    }
+                }
+                Self::Found { seed_idx, seed_dir } => crossterm::execute!(writer,
+                    Print(format_args!("seed {seed_idx}: {}\r\n", seed_dir.display())),
+                ).at_unknown()?,
+                Self::FoundTooFew { found, count, num_seeds } => crossterm::execute!(writer,
+                    Print(format_args!("only {found} of {num_seeds} seeds matched, {count} were requested\r\n")),
+                ).at_unknown()?,
+                Self::ReproProgress(msg) => crossterm::execute!(writer,
+                    MoveToColumn(0),
+                    Print(format_args!("{msg}...")),
+                    Clear(ClearType::UntilNewLine),
+                ).at_unknown()?,
+                Self::Repro { seed_idx, success, out_dir } => crossterm::execute!(writer,
+                    MoveToColumn(0),
+                    Clear(ClearType::CurrentLine),
+                    Print(format_args!("seed {seed_idx} {}, outputs written to {}\r\nrun repro.sh in that directory to roll it again\r\n", if success { "succeeded" } else { "failed" }, out_dir.display())),
+                ).at_unknown()?,
+                Self::BenchHistoryGroup { github_user, repo, label } => crossterm::execute!(writer,
+                    Print(format_args!("{github_user}/{repo}{}:\r\n", if let Some(label) = label { format!(" ({label})") } else { String::default() })),
+                ).at_unknown()?,
+                Self::BenchHistoryEntry { entry, change, regression } => crossterm::execute!(writer,
+                    Print(format_args!(
+                        "{} ({}): {:.3e} average total instructions until success, success rate {:.02}%{}{}\r\n",
+                        &entry.rev[..entry.rev.len().min(7)],
+                        entry.date.format("%Y-%m-%d"),
+                        entry.average_instructions,
+                        entry.success_rate * 100.0,
+                        if let Some(change) = change { format!(" ({:+.02}%)", change * 100.0) } else { String::default() },
+                        if regression { " — regression" } else { "" },
+                    )),
+                ).at_unknown()?,
+                Self::BisectStep { rev, num_seeds, interval, proportion, threshold, verdict } => {
+                    let format_value = |value: f64| if proportion { format!("{:.02}%", value * 100.0) } else { format!("{value:.3e}") };
                     crossterm::execute!(writer,
-                        Print(format_args!("{count}x: {top_msg} ({top_count}x, e.g. seed {seed_idx}, and {} other variants)\r\n", msgs.len())),
+                        Print(format_args!(
+                            "{rev} with {num_seeds} seeds: {} (threshold {}): {}\r\n",
+                            if let Some(Interval { estimate, lower, upper }) = interval { format!("{} ({}–{})", format_value(estimate), format_value(lower), format_value(upper)) } else { format!("no data") },
+                            format_value(threshold),
+                            match verdict {
+                                Some(true) => "bad",
+                                Some(false) => "good",
+                                None => "not significant",
+                            },
+                        )),
                     ).at_unknown()?;
-                },
+                }
+                Self::BisectResult { rev, significant } => crossterm::execute!(writer,
+                    Print(format_args!("first bad revision: {rev}{}\r\n", if significant { "" } else { " (some revisions were classified without reaching the requested confidence)" })),
+                ).at_unknown()?,
+                Self::WorldCount { world_count, num_successes, num_failures, average_instructions_success, average_instructions_failure } => crossterm::execute!(writer,
+                    Print(format_args!(
+                        "{world_count} world{}: {num_successes}/{} succeeded ({:.02}%){}{}\r\n",
+                        if world_count == 1 { "" } else { "s" },
+                        num_successes + num_failures,
+                        100.0 * f64::from(num_successes) / f64::from(num_successes + num_failures),
+                        if let Some(average_instructions_success) = average_instructions_success { format!(", average instructions (success): {average_instructions_success:.3e}") } else { String::default() },
+                        if let Some(average_instructions_failure) = average_instructions_failure { format!(", average instructions (failure): {average_instructions_failure:.3e}") } else { String::default() },
+                    )),
+                ).at_unknown()?,
+                Self::WorldCountsFit { instructions, failure_rate } => {
+                    if let Some(PowerFit { coefficient, exponent, r_squared }) = instructions {
+                        crossterm::execute!(writer,
+                            Print(format_args!("average instructions (success) ≈ {coefficient:.3e} × worlds^{exponent:.3} (R² = {r_squared:.3})\r\n")),
+                        ).at_unknown()?;
+                    } else {
+                        crossterm::execute!(writer,
+                            Print("not enough benchmarked seeds to fit instruction count curve\r\n"),
+                        ).at_unknown()?;
+                    }
+                    if let Some(LinearFit { intercept, slope, r_squared }) = failure_rate {
+                        crossterm::execute!(writer,
+                            Print(format_args!("failure rate ≈ {:.02}% + {:.02}% × worlds (R² = {r_squared:.3})\r\n", intercept * 100.0, slope * 100.0)),
+                        ).at_unknown()?;
+                    } else {
+                        crossterm::execute!(writer,
+                            Print("not enough world counts to fit failure rate curve\r\n"),
+                        ).at_unknown()?;
+                    }
+                }
             }
             crossterm::execute!(writer,
                 EndSynchronizedUpdate,
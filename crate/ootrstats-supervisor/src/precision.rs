@@ -0,0 +1,110 @@
+use {
+    std::collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    itertools::Itertools as _,
+    crate::{
+        Error,
+        SeedState,
+        analysis::{
+            Extracted,
+            WorldOutputs,
+        },
+        stats::{
+            self,
+            Interval,
+        },
+    },
+};
+
+/// The confidence level of the intervals checked by the `--until-ci` option.
+pub(crate) const CONFIDENCE: f64 = 0.95;
+
+enum Estimate {
+    /// The fraction of seeds that failed.
+    FailureRate {
+        failures: usize,
+    },
+    /// The frequencies of the outputs of the `categorize` queries among successful seeds.
+    Query {
+        /// The number of query inputs for each world (or for `None` if the queries aren't run separately for each world) and output of the `--by` query (or `None` if there is none).
+        trials: BTreeMap<(Option<u8>, Option<jaq_json::Val>), usize>,
+        /// keyed by query index, world, output of the `--by` query, and output.
+        counts: BTreeMap<(usize, Option<u8>, Option<jaq_json::Val>, jaq_json::Val), usize>,
+    },
+}
+
+/// Keeps track of the estimate targeted by the `--until-ci` option as seeds finish.
+pub(crate) struct Tracker {
+    /// The number of successful or failed seeds recorded so far.
+    pub(crate) num_seeds: usize,
+    estimate: Estimate,
+}
+
+impl Tracker {
+    /// Tracks the frequencies of the outputs of the `categorize` subcommand's queries if `categorize` is true, or the failure rate otherwise.
+    pub(crate) fn new(categorize: bool) -> Self {
+        Self {
+            num_seeds: 0,
+            estimate: if categorize {
+                Estimate::Query {
+                    trials: BTreeMap::default(),
+                    counts: BTreeMap::default(),
+                }
+            } else {
+                Estimate::FailureRate { failures: 0 }
+            },
+        }
+    }
+
+    pub(crate) fn record(&mut self, state: &SeedState) {
+        match state {
+            SeedState::Unchecked | SeedState::Pending | SeedState::Rolling { .. } | SeedState::Cancelled | SeedState::InfraFailure => return,
+            SeedState::Success { .. } => {}
+            SeedState::Failure { .. } => if let Estimate::FailureRate { ref mut failures } = self.estimate {
+                *failures += 1;
+            },
+        }
+        self.num_seeds += 1;
+    }
+
+    /// Records the query outputs of a successful seed, which may arrive after the seed itself was recorded.
+    pub(crate) fn record_extracted(&mut self, extracted: &Extracted) -> Result<(), Error> {
+        if let (Estimate::Query { trials, counts }, Extracted::Query(world_outputs)) = (&mut self.estimate, extracted) {
+            for WorldOutputs { world, outputs, by } in world_outputs {
+                // a seed is counted once for each distinct output of the --by query
+                let by = if let Some(by) = by { by.iter().map(|value| serde_json::from_value::<jaq_json::Val>(value.clone()).map(Some)).try_collect::<_, BTreeSet<_>, _>()? } else { BTreeSet::from([None]) };
+                for by in by {
+                    *trials.entry((*world, by.clone())).or_default() += 1;
+                    for (query_idx, query_outputs) in outputs.iter().enumerate() {
+                        for value in query_outputs {
+                            *counts.entry((query_idx, *world, by.clone(), serde_json::from_value(value.clone())?)).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The largest half-width of the confidence intervals of the tracked frequencies, or `None` if there's no data yet.
+    pub(crate) fn half_width(&self) -> Option<f64> {
+        let half_width = |Interval { lower, upper, .. }| (upper - lower) / 2.0;
+        match self.estimate {
+            Estimate::FailureRate { failures } => stats::wilson_interval(failures, self.num_seeds, CONFIDENCE).map(half_width),
+            Estimate::Query { ref trials, ref counts, .. } => if trials.is_empty() {
+                None
+            } else {
+                Some(counts.iter()
+                    // a query can output the same value multiple times for the same input
+                    .filter_map(|((_, world, by, _), &count)| {
+                        let trials = trials[&(*world, by.clone())];
+                        stats::wilson_interval(count.min(trials), trials, CONFIDENCE)
+                    })
+                    .map(half_width)
+                    .fold(0.0, f64::max))
+            },
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use {
+    itertools::Itertools as _,
+    crate::Error,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ToJsonError {
+    #[error(transparent)] Json(#[from] serde_json_inner::Error),
+    #[error(transparent)] Utf8(#[from] std::str::Utf8Error),
+    #[error("got a jaq object with a non-string key")]
+    KeyType,
+}
+
+/// Compiles a [JQ](https://jqlang.github.io/jq/) filter into a function returning all of the filter's outputs for a given input and value of the `$meta` variable.
+pub(crate) fn compile(query: &str) -> Result<impl Fn(jaq_json::Val, jaq_json::Val) -> Result<Vec<jaq_json::Val>, Error>, Error> {
+    let defs = jaq_core::defs().chain(jaq_std::defs()).chain(jaq_json::defs());
+    let funs = jaq_core::funs().chain(jaq_std::funs()).chain(jaq_json::funs());
+    let loader = jaq_core::load::Loader::new(defs);
+    let arena = jaq_core::load::Arena::default();
+    let program = jaq_core::load::File { code: query, path: () };
+    let modules = loader.load(&arena, program).map_err(|_| Error::JaqLoad)?;
+    let filter = jaq_core::Compiler::default()
+        .with_funs(funs)
+        .with_global_vars(["$meta"])
+        .compile(modules).map_err(|_| Error::JaqCompile)?;
+    Ok(move |input, meta| {
+        let ctx = jaq_core::Ctx::<jaq_core::data::JustLut<jaq_json::Val>>::new(&filter.lut, jaq_core::Vars::new([meta]));
+        filter.id.run((ctx, input)).map(jaq_core::unwrap_valr).map(|value| value.map_err(|_| Error::JaqRun)).collect()
+    })
+}
+
+/// Whether the given query output counts as true in JQ, i.e. is neither `false` nor `null`.
+pub(crate) fn is_truthy(value: &serde_json::Value) -> bool {
+    !matches!(value, serde_json::Value::Null | serde_json::Value::Bool(false))
+}
+
+/// Converts a query output to a JSON value, e.g. to store it.
+pub(crate) fn to_json(v: &jaq_json::Val) -> Result<serde_json::Value, ToJsonError> {
+    Ok(match v {
+        jaq_json::Val::Null => serde_json::Value::Null,
+        jaq_json::Val::Bool(b) => (*b).into(),
+        jaq_json::Val::Num(jaq_json::Num::Int(n)) => (*n).into(),
+        jaq_json::Val::Num(jaq_json::Num::Float(f)) => (*f).into(),
+        jaq_json::Val::Num(n) => serde_json::Value::Number(n.to_string().parse()?),
+        jaq_json::Val::BStr(s) | jaq_json::Val::TStr(s) => std::str::from_utf8(s)?.into(),
+        jaq_json::Val::Arr(a) => a.iter().map(to_json).try_collect()?,
+        jaq_json::Val::Obj(o) => o.iter().map(|(k, v)| Ok((match k {
+            jaq_json::Val::BStr(s) | jaq_json::Val::TStr(s) => std::str::from_utf8(s)?.to_owned(),
+            _ => return Err(ToJsonError::KeyType),
+        }, to_json(v)?))).try_collect()?,
+    })
+}
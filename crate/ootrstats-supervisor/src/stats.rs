@@ -0,0 +1,217 @@
+use serde::Serialize;
+
+/// Least-squares fit of `y = intercept + slope * x`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct LinearFit {
+    pub(crate) intercept: f64,
+    pub(crate) slope: f64,
+    pub(crate) r_squared: f64,
+}
+
+pub(crate) fn linear_fit(points: &[(f64, f64)]) -> Option<LinearFit> {
+    if points.len() < 2 { return None }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let ss_xx = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+    if ss_xx == 0.0 { return None }
+    let ss_xy = points.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+    let slope = ss_xy / ss_xx;
+    let intercept = mean_y - slope * mean_x;
+    let ss_tot = points.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum::<f64>();
+    let ss_res = points.iter().map(|&(x, y)| (y - intercept - slope * x).powi(2)).sum::<f64>();
+    Some(LinearFit {
+        r_squared: if ss_tot == 0.0 { 1.0 } else { 1.0 - ss_res / ss_tot },
+        intercept, slope,
+    })
+}
+
+/// Fit of `y = coefficient * x^exponent`, computed as a linear fit in log-log space.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct PowerFit {
+    pub(crate) coefficient: f64,
+    pub(crate) exponent: f64,
+    /// coefficient of determination of the log-log fit
+    pub(crate) r_squared: f64,
+}
+
+/// Points with non-positive coordinates are ignored since they can't be represented in log-log space.
+pub(crate) fn power_fit(points: &[(f64, f64)]) -> Option<PowerFit> {
+    let log_points = points.iter()
+        .filter(|&&(x, y)| x > 0.0 && y > 0.0)
+        .map(|&(x, y)| (x.ln(), y.ln()))
+        .collect::<Vec<_>>();
+    let LinearFit { intercept, slope, r_squared } = linear_fit(&log_points)?;
+    Some(PowerFit {
+        coefficient: intercept.exp(),
+        exponent: slope,
+        r_squared,
+    })
+}
+
+/// A point estimate with a confidence interval.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct Interval {
+    pub(crate) estimate: f64,
+    pub(crate) lower: f64,
+    pub(crate) upper: f64,
+}
+
+/// Quantile function of the standard normal distribution, using [Acklam's algorithm](https://web.archive.org/web/20151030215612/http://home.online.no/~pjacklam/notes/invnorm/).
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2, 1.383577518672690e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2, 6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    const P_LOW: f64 = 0.02425;
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+/// The `z` such that a standard normal variable is within `[-z, z]` with the given probability.
+pub(crate) fn z_score(confidence: f64) -> f64 {
+    normal_quantile((1.0 + confidence) / 2.0)
+}
+
+/// Wilson score interval for the proportion of `hits` among `trials`.
+pub(crate) fn wilson_interval(hits: usize, trials: usize, confidence: f64) -> Option<Interval> {
+    if trials == 0 { return None }
+    let n = trials as f64;
+    let estimate = hits as f64 / n;
+    let z = z_score(confidence);
+    let z2 = z * z;
+    let center = (estimate + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width = z / (1.0 + z2 / n) * (estimate * (1.0 - estimate) / n + z2 / (4.0 * n * n)).sqrt();
+    Some(Interval {
+        lower: (center - half_width).max(0.0),
+        upper: (center + half_width).min(1.0),
+        estimate,
+    })
+}
+
+/// Confidence interval for the ratio `Σy / Σx` of the given `(x, y)` samples, using the delta method.
+///
+/// With `x` = 1 for successful seeds and 0 for failed seeds, and `y` = the instructions taken for each seed, this is the average total instructions until success.
+pub(crate) fn ratio_interval(samples: &[(f64, f64)], confidence: f64) -> Option<Interval> {
+    if samples.len() < 2 { return None }
+    let n = samples.len() as f64;
+    let sum_x = samples.iter().map(|&(x, _)| x).sum::<f64>();
+    if sum_x == 0.0 { return None }
+    let estimate = samples.iter().map(|&(_, y)| y).sum::<f64>() / sum_x;
+    let mean_x = sum_x / n;
+    let variance = samples.iter().map(|&(x, y)| (y - estimate * x).powi(2)).sum::<f64>() / (n - 1.0) / (n * mean_x * mean_x);
+    let half_width = z_score(confidence) * variance.sqrt();
+    Some(Interval {
+        lower: estimate - half_width,
+        upper: estimate + half_width,
+        estimate,
+    })
+}
+
+/// Confidence interval for the difference `new - old` of two independent estimates, combining their intervals using [Newcombe's method](https://doi.org/10.1002/(SICI)1097-0258(19980430)17:8%3C873::AID-SIM779%3E3.0.CO;2-I).
+pub(crate) fn difference_interval(new: Interval, old: Interval) -> Interval {
+    let estimate = new.estimate - old.estimate;
+    Interval {
+        lower: estimate - ((new.estimate - new.lower).powi(2) + (old.upper - old.estimate).powi(2)).sqrt(),
+        upper: estimate + ((new.upper - new.estimate).powi(2) + (old.estimate - old.lower).powi(2)).sqrt(),
+        estimate,
+    }
+}
+
+/// Confidence interval for the relative change `new / old - 1` of two independent positive estimates with symmetric intervals, such as those from [`ratio_interval`], using the delta method.
+pub(crate) fn relative_change_interval(new: Interval, old: Interval) -> Interval {
+    let ratio = new.estimate / old.estimate;
+    let half_width = ratio * (((new.upper - new.lower) / 2.0 / new.estimate).powi(2) + ((old.upper - old.lower) / 2.0 / old.estimate).powi(2)).sqrt();
+    Interval {
+        estimate: ratio - 1.0,
+        lower: ratio - 1.0 - half_width,
+        upper: ratio - 1.0 + half_width,
+    }
+}
+
+/// Summary statistics of a distribution of numbers.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct Summary {
+    pub(crate) count: usize,
+    pub(crate) mean: f64,
+    pub(crate) min: f64,
+    pub(crate) p5: f64,
+    pub(crate) p25: f64,
+    pub(crate) median: f64,
+    pub(crate) p75: f64,
+    pub(crate) p95: f64,
+    pub(crate) max: f64,
+}
+
+/// `values` are pairs of a number and how often it occurs, sorted by number. Percentiles use the nearest-rank method.
+pub(crate) fn summarize(values: &[(f64, usize)]) -> Option<Summary> {
+    let count = values.iter().map(|&(_, count)| count).sum::<usize>();
+    if count == 0 { return None }
+    let percentile = |p: f64| {
+        let rank = ((p * count as f64).ceil() as usize).max(1);
+        let mut cumulative = 0;
+        values.iter().find(|&&(_, count)| {
+            cumulative += count;
+            cumulative >= rank
+        }).map_or(f64::NAN, |&(value, _)| value)
+    };
+    Some(Summary {
+        mean: values.iter().map(|&(value, count)| value * count as f64).sum::<f64>() / count as f64,
+        min: values.first()?.0,
+        p5: percentile(0.05),
+        p25: percentile(0.25),
+        median: percentile(0.5),
+        p75: percentile(0.75),
+        p95: percentile(0.95),
+        max: values.last()?.0,
+        count,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Histogram {
+    /// whether all values are integers, in which case the bins have integer bounds.
+    pub(crate) integral: bool,
+    pub(crate) bins: Vec<Bin>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct Bin {
+    pub(crate) lower: f64,
+    /// exclusive, except for the last bin.
+    pub(crate) upper: f64,
+    pub(crate) count: usize,
+}
+
+/// Sorts the given values (in the format of [`summarize`]) into at most `num_bins` bins of equal width.
+pub(crate) fn histogram(values: &[(f64, usize)], num_bins: usize) -> Option<Histogram> {
+    let min = values.first()?.0;
+    let max = values.last()?.0;
+    let integral = values.iter().all(|&(value, _)| value.fract() == 0.0);
+    let (width, num_bins) = if integral {
+        let width = ((max - min + 1.0) / num_bins as f64).ceil();
+        (width, ((max - min + 1.0) / width).ceil() as usize)
+    } else if max > min {
+        ((max - min) / num_bins as f64, num_bins)
+    } else {
+        (1.0, 1)
+    };
+    let mut bins = (0..num_bins).map(|bin_idx| Bin {
+        lower: min + bin_idx as f64 * width,
+        upper: min + (bin_idx + 1) as f64 * width,
+        count: 0,
+    }).collect::<Vec<_>>();
+    for &(value, count) in values {
+        bins[(((value - min) / width) as usize).min(num_bins - 1)].count += count;
+    }
+    Some(Histogram { integral, bins })
+}
@@ -0,0 +1,82 @@
+use {
+    std::{
+        num::NonZero,
+        path::{
+            Path,
+            PathBuf,
+        },
+    },
+    serde::Deserialize,
+    wheel::fs,
+    ootrstats::SeedIdx,
+};
+#[cfg(windows)] use directories::ProjectDirs;
+#[cfg(unix)] use xdg::BaseDirectories;
+
+/// A benchmarking suite loaded from a TOML or JSON file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Suite {
+    pub(crate) entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Entry {
+    pub(crate) label: String,
+    #[serde(default)]
+    pub(crate) rsl: bool,
+    pub(crate) preset: Option<String>,
+    pub(crate) settings: Option<String>,
+    pub(crate) draft: Option<PathBuf>,
+    #[serde(default)]
+    pub(crate) json_settings: serde_json::Map<String, serde_json::Value>,
+    pub(crate) num_seeds: Option<NonZero<SeedIdx>>,
+    /// If any of `githubUser`, `repo`, or `branch` is specified, the entry uses its own repository instead of the one given on the command line.
+    pub(crate) github_user: Option<String>,
+    pub(crate) repo: Option<String>,
+    pub(crate) branch: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error(transparent)] Toml(#[from] toml::de::Error),
+    #[error(transparent)] Wheel(#[from] wheel::Error),
+    #[cfg(windows)]
+    #[error("user folder not found")]
+    MissingHomeDir,
+    #[error("no suite named {0:?} found")]
+    NotFound(String),
+}
+
+impl Suite {
+    /// Loads the suite from the given path if it exists. Otherwise, looks for a file named `<name>.toml` or `<name>.json` in the `suites` folder next to the config file.
+    ///
+    /// The name `default` refers to the built-in suite.
+    pub(crate) async fn load(name_or_path: &str) -> Result<Self, Error> {
+        if name_or_path == "default" {
+            return Ok(toml::from_str(include_str!("default-suite.toml"))?)
+        }
+        if fs::exists(name_or_path).await? {
+            return Self::load_file(Path::new(name_or_path)).await
+        }
+        for ext in ["toml", "json"] {
+            #[cfg(unix)] let path = BaseDirectories::new().find_config_file(format!("ootrstats/suites/{name_or_path}.{ext}"));
+            #[cfg(windows)] let path = Some(ProjectDirs::from("net", "Fenhl", "ootrstats").ok_or(Error::MissingHomeDir)?.config_dir().join("suites").join(format!("{name_or_path}.{ext}")));
+            if let Some(path) = path {
+                if fs::exists(&path).await? {
+                    return Self::load_file(&path).await
+                }
+            }
+        }
+        Err(Error::NotFound(name_or_path.to_owned()))
+    }
+
+    async fn load_file(path: &Path) -> Result<Self, Error> {
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Ok(toml::from_str(&fs::read_to_string(path).await?)?)
+        } else {
+            Ok(fs::read_json(path).await?)
+        }
+    }
+}
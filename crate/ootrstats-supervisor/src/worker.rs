@@ -64,6 +64,7 @@ use {
         OutputMode,
         RandoSetup,
         SeedIdx,
+        Side,
         websocket,
         worker::{
             Message,
@@ -168,11 +169,11 @@ impl IsNetworkError for Error {
 }
 
 impl Kind {
-    async fn run(self, name: Arc<str>, tx: mpsc::Sender<(Arc<str>, Message)>, mut rx: mpsc::Receiver<SupervisorMessage>, rando_rev: gix::ObjectId, setup: RandoSetup, output_mode: OutputMode, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<Vec<PathBuf>>, race: bool) -> Result<(), Error> {
+    async fn run(self, name: Arc<str>, tx: mpsc::Sender<(Arc<str>, Message)>, mut rx: mpsc::Receiver<SupervisorMessage>, rando_rev: gix::ObjectId, ab_rev: Option<gix::ObjectId>, setup: RandoSetup, output_mode: OutputMode, seed_timeout: Option<Duration>, memory_limit: Option<ByteSize>, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<Vec<PathBuf>>, race: bool) -> Result<(), Error> {
         match self {
             Self::Local { base_rom_path, wsl_distro, cores } => {
                 let (inner_tx, mut inner_rx) = mpsc::channel(256);
-                let mut work = pin!(ootrstats::worker::work(false, inner_tx, rx, base_rom_path.clone(), cores, wsl_distro, rando_rev, setup, output_mode, min_disk, min_disk_percent, min_disk_mount_points.as_deref(), &[], race));
+                let mut work = pin!(ootrstats::worker::work(false, inner_tx, rx, base_rom_path.clone(), cores, wsl_distro, rando_rev, ab_rev, setup, output_mode, seed_timeout, memory_limit, min_disk, min_disk_percent, min_disk_mount_points.as_deref(), &[], race));
                 loop {
                     select! {
                         res = &mut work => {
@@ -200,7 +201,7 @@ impl Kind {
                 tx.send((name.clone(), Message::Init(format!("handshaking")))).await?;
                 sink.send(websocket::ClientMessage::Handshake {
                     min_disk_mount_points: min_disk_mount_points.map(|mp| mp.into_iter().map(|p| p.into_os_string().into_string()).collect::<Result<_, _>>()).transpose()?,
-                    password, wsl_distro, rando_rev, setup, output_mode, min_disk, min_disk_percent, priority_users, race, hide_reboot, hide_sleep,
+                    password, wsl_distro, rando_rev, ab_rev, setup, output_mode, seed_timeout, memory_limit, min_disk, min_disk_percent, priority_users, race, hide_reboot, hide_sleep,
                 }).await?;
                 tx.send((name.clone(), Message::Init(format!("waiting for reply from worker")))).await?;
                 let mut ping_interval = interval(Duration::from_secs(30));
@@ -211,15 +212,15 @@ impl Kind {
                         res = timeout(Duration::from_secs(60), stream.next().then(|opt| if let Some(res) = opt { Either::Left(future::ready(res)) } else { Either::Right(future::pending()) })) => match res? {
                             Ok(websocket::ServerMessage::Init(msg)) => tx.send((name.clone(), Message::Init(msg))).await?,
                             Ok(websocket::ServerMessage::Ready(ready)) => tx.send((name.clone(), Message::Ready(ready))).await?,
-                            Ok(websocket::ServerMessage::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }) => tx.send((name.clone(), Message::Success {
+                            Ok(websocket::ServerMessage::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }) => tx.send((name.clone(), Message::Success {
                                 spoiler_log: Either::Right(spoiler_log),
                                 patch: patch.map(Either::Right),
                                 rsl_plando: rsl_plando.map(Either::Right),
-                                seed_idx, instructions, rsl_instructions,
+                                side, seed_idx, instructions, rsl_instructions,
                             })).await?,
-                            Ok(websocket::ServerMessage::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando }) => tx.send((name.clone(), Message::Failure {
+                            Ok(websocket::ServerMessage::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando }) => tx.send((name.clone(), Message::Failure {
                                 rsl_plando: rsl_plando.map(Either::Right),
-                                seed_idx, instructions, rsl_instructions, error_log,
+                                side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory,
                             })).await?,
                             Ok(websocket::ServerMessage::Error { display, debug }) => return Err(Error::Remote { debug, display }),
                             Ok(websocket::ServerMessage::Ping) => {}
@@ -235,15 +236,15 @@ impl Kind {
                                 match res {
                                     Ok(websocket::ServerMessage::Init(msg)) => tx.send((name.clone(), Message::Init(msg))).await?,
                                     Ok(websocket::ServerMessage::Ready(ready)) => tx.send((name.clone(), Message::Ready(ready))).await?,
-                                    Ok(websocket::ServerMessage::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }) => tx.send((name.clone(), Message::Success {
+                                    Ok(websocket::ServerMessage::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }) => tx.send((name.clone(), Message::Success {
                                         spoiler_log: Either::Right(spoiler_log),
                                         patch: patch.map(Either::Right),
                                         rsl_plando: rsl_plando.map(Either::Right),
-                                        seed_idx, instructions, rsl_instructions,
+                                        side, seed_idx, instructions, rsl_instructions,
                                     })).await?,
-                                    Ok(websocket::ServerMessage::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando }) => tx.send((name.clone(), Message::Failure {
+                                    Ok(websocket::ServerMessage::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando }) => tx.send((name.clone(), Message::Failure {
                                         rsl_plando: rsl_plando.map(Either::Right),
-                                        seed_idx, instructions, rsl_instructions, error_log,
+                                        side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory,
                                     })).await?,
                                     Ok(websocket::ServerMessage::Error { display, debug }) => return Err(Error::Remote { debug, display }),
                                     Ok(websocket::ServerMessage::Ping) => {}
@@ -384,20 +385,20 @@ impl State {
         }
     }
 
-    pub(crate) fn connect(&mut self, worker_tx: mpsc::Sender<(Arc<str>, Message)>, kind: Kind, rando_rev: gix::ObjectId, setup: &RandoSetup, output_mode: OutputMode, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<Vec<PathBuf>>, race: bool) -> JoinHandle<Result<(), Error>> {
+    pub(crate) fn connect(&mut self, worker_tx: mpsc::Sender<(Arc<str>, Message)>, kind: Kind, rando_rev: gix::ObjectId, ab_rev: Option<gix::ObjectId>, setup: &RandoSetup, output_mode: OutputMode, seed_timeout: Option<Duration>, memory_limit: Option<ByteSize>, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<Vec<PathBuf>>, race: bool) -> JoinHandle<Result<(), Error>> {
         self.prev_error = self.error.take();
         let (supervisor_tx, supervisor_rx) = mpsc::channel(256);
         self.supervisor_tx = Some(supervisor_tx);
-        tokio::spawn(kind.run(self.name.clone(), worker_tx, supervisor_rx, rando_rev, setup.clone(), output_mode, min_disk, min_disk_percent, min_disk_mount_points, race))
+        tokio::spawn(kind.run(self.name.clone(), worker_tx, supervisor_rx, rando_rev, ab_rev, setup.clone(), output_mode, seed_timeout, memory_limit, min_disk, min_disk_percent, min_disk_mount_points, race))
     }
 
-    pub(crate) async fn roll(&mut self, seed_states: &mut [SeedState], seed_idx: SeedIdx) -> Result<(), mpsc::error::SendError<SupervisorMessage>> {
-        self.supervisor_tx.as_ref().expect("attempted to roll a seed on an uninitialized worker").send(SupervisorMessage::Roll(seed_idx)).await?;
+    pub(crate) async fn roll(&mut self, seed_state: &mut SeedState, side: Side, seed_idx: SeedIdx) -> Result<(), mpsc::error::SendError<SupervisorMessage>> {
+        self.supervisor_tx.as_ref().expect("attempted to roll a seed on an uninitialized worker").send(SupervisorMessage::Roll(side, seed_idx)).await?;
         self.ready -= 1;
-        if let SeedState::Rolling { ref mut workers } = seed_states[usize::from(seed_idx)] {
+        if let SeedState::Rolling { ref mut workers } = *seed_state {
             workers.push(self.name.clone());
         } else {
-            seed_states[usize::from(seed_idx)] = SeedState::Rolling { workers: nev![self.name.clone()] };
+            *seed_state = SeedState::Rolling { workers: nev![self.name.clone()] };
         }
         Ok(())
     }
@@ -62,7 +62,7 @@ enum Error {
 }
 
 async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: Arc<Mutex<SplitSink<rocket_ws::stream::DuplexStream, rocket_ws::Message>>>, stream: &mut SplitStream<rocket_ws::stream::DuplexStream>, #[cfg_attr(not(windows), allow(unused))] unhide_reboot: &mut bool, #[cfg_attr(not(windows), allow(unused))] unhide_sleep: &mut bool) -> Result<(), Error> {
-    let websocket::ClientMessage::Handshake { password: received_password, wsl_distro, rando_rev, setup, output_mode, min_disk, min_disk_percent, min_disk_mount_points, priority_users, race, hide_reboot, hide_sleep } = websocket::ClientMessage::read_ws021(stream).await? else { return Ok(()) };
+    let websocket::ClientMessage::Handshake { password: received_password, wsl_distro, rando_rev, ab_rev, setup, output_mode, seed_timeout, memory_limit, min_disk, min_disk_percent, min_disk_mount_points, priority_users, race, hide_reboot, hide_sleep } = websocket::ClientMessage::read_ws021(stream).await? else { return Ok(()) };
     if !constant_time_eq(received_password.as_bytes(), correct_password.as_bytes()) { return Ok(()) }
     #[cfg(windows)] {
         if hide_reboot {
@@ -82,7 +82,7 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
     let (mut supervisor_tx, supervisor_rx) = mpsc::channel(256);
     let mut stream = Some(stream);
     let min_disk_mount_points = min_disk_mount_points.map(|mp| mp.into_iter().map(PathBuf::from).collect_vec());
-    let mut work = pin!(ootrstats::worker::work(true, worker_tx, supervisor_rx, base_rom_path, cores, wsl_distro, rando_rev, setup, output_mode, min_disk, min_disk_percent, min_disk_mount_points.as_deref(), &priority_users, race));
+    let mut work = pin!(ootrstats::worker::work(true, worker_tx, supervisor_rx, base_rom_path, cores, wsl_distro, rando_rev, ab_rev, setup, output_mode, seed_timeout, memory_limit, min_disk, min_disk_percent, min_disk_mount_points.as_deref(), &priority_users, race));
     loop {
         let next_msg = if let Some(ref mut stream) = stream {
             Either::Left(timeout(Duration::from_secs(60), websocket::ClientMessage::read_ws021(*stream)))
@@ -102,7 +102,7 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
                     match msg {
                         ootrstats::worker::Message::Init(msg) => lock!(sink = sink; websocket::ServerMessage::Init(msg).write_ws021(&mut *sink).await)?,
                         ootrstats::worker::Message::Ready(ready) => lock!(sink = sink; websocket::ServerMessage::Ready(ready).write_ws021(&mut *sink).await)?,
-                        ootrstats::worker::Message::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => {
+                        ootrstats::worker::Message::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => {
                             let spoiler_log = match spoiler_log {
                                 Either::Left(spoiler_log_path) => {
                                     let spoiler_log = fs::read(&spoiler_log_path).await?.into();
@@ -147,9 +147,9 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
                                 Some(Either::Right(rsl_plando)) => Some(rsl_plando),
                                 None => None,
                             };
-                            lock!(sink = sink; websocket::ServerMessage::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }.write_ws021(&mut *sink).await)?;
+                            lock!(sink = sink; websocket::ServerMessage::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }.write_ws021(&mut *sink).await)?;
                         }
-                        ootrstats::worker::Message::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando } => {
+                        ootrstats::worker::Message::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando } => {
                             let rsl_plando = match rsl_plando {
                                 Some(Either::Left(rsl_plando_path)) => {
                                     let rsl_plando = fs::read(&rsl_plando_path).await?.into();
@@ -159,7 +159,7 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
                                 Some(Either::Right(rsl_plando)) => Some(rsl_plando),
                                 None => None,
                             };
-                            lock!(sink = sink; websocket::ServerMessage::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando }.write_ws021(&mut *sink).await)?;
+                            lock!(sink = sink; websocket::ServerMessage::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando }.write_ws021(&mut *sink).await)?;
                         }
                     }
                 }
@@ -169,7 +169,7 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
             Some(msg) = worker_rx.recv() => match msg {
                 ootrstats::worker::Message::Init(msg) => lock!(sink = sink; websocket::ServerMessage::Init(msg).write_ws021(&mut *sink).await)?,
                 ootrstats::worker::Message::Ready(ready) => lock!(sink = sink; websocket::ServerMessage::Ready(ready).write_ws021(&mut *sink).await)?,
-                ootrstats::worker::Message::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => {
+                ootrstats::worker::Message::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando } => {
                     let spoiler_log = match spoiler_log {
                         Either::Left(spoiler_log_path) => {
                             let spoiler_log = fs::read(&spoiler_log_path).await?.into();
@@ -214,9 +214,9 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
                         Some(Either::Right(rsl_plando)) => Some(rsl_plando),
                         None => None,
                     };
-                    lock!(sink = sink; websocket::ServerMessage::Success { seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }.write_ws021(&mut *sink).await)?;
+                    lock!(sink = sink; websocket::ServerMessage::Success { side, seed_idx, instructions, rsl_instructions, spoiler_log, patch, rsl_plando }.write_ws021(&mut *sink).await)?;
                 }
-                ootrstats::worker::Message::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando } => {
+                ootrstats::worker::Message::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando } => {
                     let rsl_plando = match rsl_plando {
                         Some(Either::Left(rsl_plando_path)) => {
                             let rsl_plando = fs::read(&rsl_plando_path).await?.into();
@@ -226,7 +226,7 @@ async fn work(base_rom_path: PathBuf, correct_password: &str, cores: i8, sink: A
                         Some(Either::Right(rsl_plando)) => Some(rsl_plando),
                         None => None,
                     };
-                    lock!(sink = sink; websocket::ServerMessage::Failure { seed_idx, instructions, rsl_instructions, error_log, rsl_plando }.write_ws021(&mut *sink).await)?;
+                    lock!(sink = sink; websocket::ServerMessage::Failure { side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory, rsl_plando }.write_ws021(&mut *sink).await)?;
                 }
             },
             res = next_msg => match res?? {
@@ -148,9 +148,10 @@ pub struct Spec {
 }
 
 impl Spec {
-    pub(crate) fn complete_randomly(&self) -> Result<HashMap<Cow<'static, str>, Json>, ResolveError> {
+    /// Simulates the draft with random picks. If a seed is given, the same picks are made every time.
+    pub(crate) fn complete_randomly(&self, seed: Option<u64>) -> Result<HashMap<Cow<'static, str>, Json>, ResolveError> {
         let Self { groups, steps, settings } = self;
-        let mut rng = rng();
+        let mut rng = StdRng::seed_from_u64(seed.unwrap_or_else(|| rng().random()));
         let mut has_picked = HashSet::new();
         let mut picked_settings = HashMap::<&str, &str>::default();
         let fr_5_triforce_count_per_world = rng.random_range(50..=100).to_string();
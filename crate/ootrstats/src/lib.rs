@@ -13,9 +13,11 @@ use {
             PathBuf,
         },
         process::Stdio,
+        time::Duration,
     },
     async_proto::Protocol,
     bytes::Bytes,
+    bytesize::ByteSize,
     collect_mac::collect,
     cross_path::CrossPath,
     directories::{
@@ -23,17 +25,33 @@ use {
         UserDirs,
     },
     either::Either,
+    futures::future,
     if_chain::if_chain,
     itertools::Itertools as _,
-    lazy_regex::regex_captures,
+    lazy_regex::{
+        regex_captures,
+        regex_is_match,
+    },
     reqwest as _, // crate features required to configure gix
     rustc_stable_hash::StableSipHasher128,
     rustls as _, // crate features required to configure reqwest
     semver::Version,
     serde_json::json,
     tokio::{
-        io::AsyncWriteExt as _,
-        process::Command,
+        io::{
+            AsyncReadExt as _,
+            AsyncWriteExt as _,
+        },
+        process::{
+            Child,
+            Command,
+        },
+        select,
+        time::{
+            Instant,
+            sleep_until,
+            timeout,
+        },
     },
     wheel::{
         fs,
@@ -43,7 +61,10 @@ use {
         },
     },
 };
-#[cfg(unix)] use xdg::BaseDirectories;
+#[cfg(unix)] use {
+    std::os::unix::process::ExitStatusExt as _,
+    xdg::BaseDirectories,
+};
 
 mod draft;
 pub mod websocket;
@@ -52,7 +73,7 @@ pub mod worker;
 /// install using `wsl --update --pre-release` to get support for the CPU instruction counter and SSH access
 pub const WSL: &str = "C:\\Program Files\\WSL\\wsl.exe";
 
-pub type SeedIdx = u16;
+pub type SeedIdx = u32;
 
 #[derive(Clone, Protocol)]
 pub enum RandoSetup {
@@ -62,7 +83,8 @@ pub enum RandoSetup {
         settings: RandoSettings,
         json_settings: serde_json::Map<String, serde_json::Value>,
         plando: serde_json::Map<String, serde_json::Value>,
-        world_counts: bool,
+        world_counts: Option<WorldCounts>,
+        sweeps: Vec<Sweep>,
         seeds: Seeds,
     },
     Rsl {
@@ -76,7 +98,7 @@ pub enum RandoSetup {
 impl RandoSetup {
     pub fn stats_dir(&self, rando_rev: gix::ObjectId) -> PathBuf {
         match self {
-            Self::Normal { github_user, repo, settings, json_settings, plando, world_counts, seeds } => {
+            Self::Normal { github_user, repo, settings, json_settings, plando, world_counts, sweeps, seeds } => {
                 let mut path = Path::new("rando")
                     .join(github_user)
                     .join(repo)
@@ -92,13 +114,32 @@ impl RandoSetup {
                     plando.hash(&mut hasher);
                     path = path.join(format!("p{:016x}", Hasher::finish(&hasher))).into();
                 }
-                match (world_counts, seeds) {
-                    (false, Seeds::Default) => path.join("default"),
-                    (false, Seeds::Random) => path.join("r"),
-                    (false, Seeds::Fixed(seed)) => path.join("s").join(seed),
-                    (true, Seeds::Default) => path.join("w"),
-                    (true, Seeds::Random) => path.join("wr"),
-                    (true, Seeds::Fixed(seed)) => path.join("ws").join(seed),
+                if !sweeps.is_empty() {
+                    let mut hasher = StableSipHasher128::default();
+                    sweeps.hash(&mut hasher);
+                    path = path.join(format!("x{:016x}", Hasher::finish(&hasher))).into();
+                }
+                if let Some(world_counts) = world_counts {
+                    // seeds_per_value only determines how many seeds are rolled, not which world count each seed has, so it's not part of the stats dir
+                    let prefix = if world_counts.values == WorldCounts::default().values {
+                        // same as the stats dir used by ootrstats versions which only supported this distribution
+                        format!("w")
+                    } else {
+                        let mut hasher = StableSipHasher128::default();
+                        world_counts.values.hash(&mut hasher);
+                        format!("w{:016x}", Hasher::finish(&hasher))
+                    };
+                    match seeds {
+                        Seeds::Default => path.join(prefix),
+                        Seeds::Random => path.join(format!("{prefix}r")),
+                        Seeds::Fixed(seed) => path.join(format!("{prefix}s")).join(seed),
+                    }
+                } else {
+                    match seeds {
+                        Seeds::Default => path.join("default"),
+                        Seeds::Random => path.join("r"),
+                        Seeds::Fixed(seed) => path.join("s").join(seed),
+                    }
                 }
             }
             Self::Rsl { github_user, repo, preset, seeds } => {
@@ -142,12 +183,84 @@ impl RandoSettings {
             Self::Draft(spec) => {
                 let mut hasher = StableSipHasher128::default();
                 spec.hash(&mut hasher);
-                Path::new("draft").join(format!("{:016x}", Hasher::finish(&hasher))).into()
+                // the prefix distinguishes seeds with picks determined by the seed from seeds rolled by older ootrstats versions, whose picks were unseeded
+                Path::new("draft").join(format!("s{:016x}", Hasher::finish(&hasher))).into()
             }
         }
     }
 }
 
+/// A distribution of world counts for multiworld seeds.
+///
+/// Seeds are assigned to world counts in rounds, with each round containing each world count as many times as its weight, so any prefix of the seed indices samples all world counts.
+#[derive(Clone, PartialEq, Eq, Hash, Protocol)]
+pub struct WorldCounts {
+    /// Each world count along with its weight.
+    pub values: Vec<(u8, u16)>,
+    pub seeds_per_value: u16,
+}
+
+impl WorldCounts {
+    /// The number of seeds in one round.
+    fn round_len(&self) -> usize {
+        self.values.iter().map(|&(_, weight)| usize::from(weight)).sum()
+    }
+
+    /// The total number of seeds in this distribution.
+    pub fn num_seeds(&self) -> usize {
+        self.round_len() * usize::from(self.seeds_per_value)
+    }
+
+    /// Returns `None` if the seed index is outside of this distribution.
+    pub fn world_count(&self, seed_idx: SeedIdx) -> Option<u8> {
+        let seed_idx = seed_idx as usize;
+        if seed_idx >= self.num_seeds() { return None }
+        let mut round_idx = seed_idx % self.round_len();
+        for &(world_count, weight) in &self.values {
+            if round_idx < usize::from(weight) { return Some(world_count) }
+            round_idx -= usize::from(weight);
+        }
+        unreachable!("round index out of range")
+    }
+}
+
+impl Default for WorldCounts {
+    /// The distribution used by the `--world-counts` option of older ootrstats versions: one seed each with 1 to 255 worlds.
+    fn default() -> Self {
+        Self {
+            values: (1..=255).map(|world_count| (world_count, 1)).collect(),
+            seeds_per_value: 1,
+        }
+    }
+}
+
+/// A setting whose value varies between seeds.
+#[derive(Clone, Hash, Protocol)]
+pub struct Sweep {
+    pub setting: String,
+    pub values: Vec<serde_json::Value>,
+}
+
+/// The number of combinations of values of the given sweeps.
+pub fn num_sweep_points(sweeps: &[Sweep]) -> usize {
+    sweeps.iter().map(|sweep| sweep.values.len()).product()
+}
+
+/// Seeds are assigned to sweep points round-robin, so any prefix of the seed indices covers all sweep points evenly.
+pub fn sweep_point_idx(sweeps: &[Sweep], seed_idx: SeedIdx) -> usize {
+    seed_idx as usize % num_sweep_points(sweeps)
+}
+
+/// The setting values at the given sweep point. The values of the first sweep vary fastest.
+pub fn sweep_point(sweeps: &[Sweep], point_idx: usize) -> serde_json::Map<String, serde_json::Value> {
+    let mut remaining = point_idx;
+    sweeps.iter().map(|Sweep { setting, values }| {
+        let value = values[remaining % values.len()].clone();
+        remaining /= values.len();
+        (setting.clone(), value)
+    }).collect()
+}
+
 #[derive(Clone, Protocol)]
 pub enum Seeds {
     Default,
@@ -155,6 +268,15 @@ pub enum Seeds {
     Fixed(String),
 }
 
+/// Which of the two revisions of an A/B run a seed is rolled on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Protocol)]
+pub enum Side {
+    /// The main revision of the run.
+    A,
+    /// The revision given by the supervisor's `--ab-rev` option.
+    B,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Protocol)]
 pub enum OutputMode {
     Normal {
@@ -174,6 +296,12 @@ pub struct RollOutput {
     /// `(is_wsl, path)`
     pub patch: Option<(bool, PathBuf)>,
     pub rsl_plando: Option<PathBuf>,
+    /// the signal which terminated the randomizer, if any. Always `None` on Windows.
+    pub exit_signal: Option<i32>,
+    /// whether the randomizer (or the random settings script) was killed for exceeding the seed timeout. If so, `log` contains the stderr output up to that point.
+    pub timed_out: bool,
+    /// whether the randomizer (or the random settings script) failed because it exceeded the memory limit.
+    pub out_of_memory: bool,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -263,7 +391,18 @@ async fn python() -> Result<PathBuf, RollError> {
     })
 }
 
-pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_base_rom_path: &Path, use_rust_cli: bool, supports_unsalted_seeds: bool, creates_log_by_default: bool, seeds: Seeds, settings: &RandoSettings, json_settings: &serde_json::Map<String, serde_json::Value>, plando: Option<&Path>, world_counts: bool, seed_idx: SeedIdx, output_mode: OutputMode) -> Result<RollOutput, RollError> {
+/// Builds the command which rolls a seed with the randomizer, along with its name for error messages and the settings to be passed to it on stdin.
+async fn rando_command(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_base_rom_path: &Path, use_rust_cli: bool, supports_unsalted_seeds: bool, creates_log_by_default: bool, seeds: Seeds, settings: &RandoSettings, json_settings: &serde_json::Map<String, serde_json::Value>, plando: Option<&Path>, world_count: Option<u8>, seed_idx: SeedIdx, output_mode: OutputMode) -> Result<(Command, String, Vec<u8>), RollError> {
+    // draft picks are derived from the seed so they can be reproduced
+    let draft_seed = match seeds {
+        Seeds::Default => Some(format!("ootrstats{seed_idx}")),
+        Seeds::Random => None,
+        Seeds::Fixed(ref seed) => Some(format!("{seed}/{seed_idx}")),
+    }.map(|seed| {
+        let mut hasher = StableSipHasher128::default();
+        seed.hash(&mut hasher);
+        Hasher::finish(&hasher)
+    });
     let mut resolved_settings = collect![as HashMap<_, _>:
         Cow::Borrowed("rom") => if let OutputMode::Bench { .. } = output_mode {
             json!(CrossPath::from(uncompressed_base_rom_path).to_unix()?)
@@ -285,8 +424,8 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
         resolved_settings.insert(Cow::Borrowed("enable_distribution_file"), json!(true));
         resolved_settings.insert(Cow::Borrowed("distribution_file"), json!(plando));
     }
-    if world_counts {
-        resolved_settings.insert(Cow::Borrowed("world_count"), json!(seed_idx + 1));
+    if let Some(world_count) = world_count {
+        resolved_settings.insert(Cow::Borrowed("world_count"), json!(world_count));
     }
     let mut cmd_name;
     let mut cmd;
@@ -347,7 +486,7 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
                 cmd.arg("--settings-string");
                 cmd.arg(settings);
             }
-            RandoSettings::Draft(spec) => resolved_settings.extend(spec.complete_randomly()?),
+            RandoSettings::Draft(spec) => resolved_settings.extend(spec.complete_randomly(draft_seed)?),
         }
     } else {
         let python = python().await?;
@@ -405,7 +544,7 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
                 cmd.arg("--settings_string");
                 cmd.arg(settings);
             }
-            RandoSettings::Draft(spec) => resolved_settings.extend(spec.complete_randomly()?),
+            RandoSettings::Draft(spec) => resolved_settings.extend(spec.complete_randomly(draft_seed)?),
         }
     }
     cmd.arg("--settings=-");
@@ -417,15 +556,96 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
             cmd.arg(seed);
         }
     }
+    cmd.current_dir(repo_path);
+    cmd.kill_on_drop(true);
+    Ok((cmd, cmd_name, serde_json::to_vec(&resolved_settings)?))
+}
+
+/// Wraps the given command in `prlimit` so that its (and its subprocesses') allocations fail once they exceed the given size.
+///
+/// The limit is on the data segment, i.e. on private writable memory, since limiting address space would also count memory that's reserved but never used.
+#[cfg(target_os = "linux")]
+fn limit_memory(cmd: &Command, cmd_name: &mut String, memory_limit: ByteSize) -> Command {
+    let inner = cmd.as_std();
+    let mut limited = Command::new("prlimit");
+    limited.arg(format!("--data={}", memory_limit.as_u64()));
+    limited.arg("--");
+    limited.arg(inner.get_program());
+    limited.args(inner.get_args());
+    for (key, value) in inner.get_envs() {
+        if let Some(value) = value {
+            limited.env(key, value);
+        } else {
+            limited.env_remove(key);
+        }
+    }
+    if let Some(current_dir) = inner.get_current_dir() {
+        limited.current_dir(current_dir);
+    }
+    limited.kill_on_drop(true);
+    *cmd_name = format!("prlimit {cmd_name}");
+    limited
+}
+
+/// Waits for the given process to exit, killing it if it's still running at the deadline. Returns its output and whether it timed out.
+///
+/// On Unix, the process must have been spawned in its own process group if there's a deadline, so that its subprocesses are killed as well.
+async fn wait_until(mut child: Child, cmd_name: &str, deadline: Option<Instant>) -> Result<(std::process::Output, bool), RollError> {
+    // output is read incrementally so the output up to that point is available if the process times out
+    let mut stdout_pipe = child.stdout.take();
+    let mut stderr_pipe = child.stderr.take();
+    let mut stdout = Vec::default();
+    let mut stderr = Vec::default();
+    let mut timed_out = false;
+    while stdout_pipe.is_some() || stderr_pipe.is_some() {
+        select! {
+            res = async { stdout_pipe.as_mut().expect("checked by branch condition").read_buf(&mut stdout).await }, if stdout_pipe.is_some() => if res.at_command(cmd_name.to_owned())? == 0 { stdout_pipe = None },
+            res = async { stderr_pipe.as_mut().expect("checked by branch condition").read_buf(&mut stderr).await }, if stderr_pipe.is_some() => if res.at_command(cmd_name.to_owned())? == 0 { stderr_pipe = None },
+            () = async { if let Some(deadline) = deadline { sleep_until(deadline).await } else { future::pending().await } } => {
+                timed_out = true;
+                break
+            }
+        }
+    }
+    if timed_out {
+        #[cfg(unix)] if let Some(pid) = child.id() {
+            Command::new("kill").arg("-KILL").arg("--").arg(format!("-{pid}")).check("kill").await?;
+        }
+        child.kill().await.at_command(cmd_name.to_owned())?;
+        // pick up any output which was still buffered in the pipes
+        if let Some(mut stdout_pipe) = stdout_pipe {
+            let _ = timeout(Duration::from_secs(5), stdout_pipe.read_to_end(&mut stdout)).await;
+        }
+        if let Some(mut stderr_pipe) = stderr_pipe {
+            let _ = timeout(Duration::from_secs(5), stderr_pipe.read_to_end(&mut stderr)).await;
+        }
+    }
+    Ok((std::process::Output {
+        status: child.wait().await.at_command(cmd_name.to_owned())?,
+        stdout, stderr,
+    }, timed_out))
+}
+
+pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_base_rom_path: &Path, use_rust_cli: bool, supports_unsalted_seeds: bool, creates_log_by_default: bool, seeds: Seeds, settings: &RandoSettings, json_settings: &serde_json::Map<String, serde_json::Value>, plando: Option<&Path>, world_count: Option<u8>, seed_idx: SeedIdx, output_mode: OutputMode, seed_timeout: Option<Duration>, memory_limit: Option<ByteSize>) -> Result<RollOutput, RollError> {
+    #[cfg_attr(not(target_os = "linux"), allow(unused_mut))] let (mut cmd, mut cmd_name, input) = rando_command(wsl_distro, repo_path, uncompressed_base_rom_path, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, settings, json_settings, plando, world_count, seed_idx, output_mode).await?;
+    #[cfg(target_os = "linux")] if let Some(memory_limit) = memory_limit {
+        cmd = limit_memory(&cmd, &mut cmd_name, memory_limit);
+    }
     cmd.stdin(Stdio::piped());
     cmd.stdout(Stdio::null());
     cmd.stderr(Stdio::piped());
-    cmd.current_dir(repo_path);
-    cmd.kill_on_drop(true);
+    #[cfg(unix)] if seed_timeout.is_some() {
+        // allows killing subprocesses of the randomizer (or of `perf`) on timeout
+        cmd.process_group(0);
+    }
     let mut child = cmd.spawn().at_command(cmd_name.clone())?;
-    child.stdin.as_mut().expect("configured").write_all(&serde_json::to_vec(&resolved_settings)?).await.at_command(cmd_name.clone())?;
-    let output = child.wait_with_output().await.at_command(cmd_name.clone())?;
+    let mut stdin = child.stdin.take().expect("configured");
+    stdin.write_all(&input).await.at_command(cmd_name.clone())?;
+    drop(stdin);
+    let (output, timed_out) = wait_until(child, &cmd_name, seed_timeout.map(|seed_timeout| Instant::now() + seed_timeout)).await?;
     let stderr = BufRead::lines(&*output.stderr).try_collect::<_, Vec<_>, _>().at_command(cmd_name)?;
+    // allocations fail once the limit is reached, which Python reports as a MemoryError and Rust as an abort
+    let out_of_memory = cfg!(target_os = "linux") && memory_limit.is_some() && !output.status.success() && !timed_out && stderr.iter().any(|line| regex_is_match!("^MemoryError(?::|$)|^memory allocation of [0-9]+ bytes failed", line));
     if output.status.success() {
         if let Some(distribution_file_path) = stderr.iter().rev().find_map(|line| line.strip_prefix("Copied distribution file to: ")) {
             if cfg!(target_os = "windows") && matches!(output_mode, OutputMode::Bench { .. }) {
@@ -473,6 +693,10 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
             }
         }
     }
+    let exit_signal = {
+        #[cfg(unix)] { output.status.signal() }
+        #[cfg(not(unix))] { None }
+    };
     Ok(RollOutput {
         instructions: if let OutputMode::Bench { .. } = output_mode {
             #[cfg(any(target_os = "linux", target_os = "windows"))] {
@@ -521,10 +745,11 @@ pub async fn run_rando(wsl_distro: Option<&str>, repo_path: &Path, uncompressed_
         },
         rsl_instructions: Ok(0),
         rsl_plando: None,
+        exit_signal, timed_out, out_of_memory,
     })
 }
 
-pub async fn run_rsl(#[cfg_attr(not(target_os = "windows"), allow(unused))] wsl_distro: Option<&str>, repo_path: &Path, rsl_version: &str, use_rust_cli: bool, supports_unsalted_seeds: bool, creates_log_by_default: bool, seeds: Seeds, preset: Option<&Either<String, serde_json::Map<String, serde_json::Value>>>, seed_idx: SeedIdx, output_mode: OutputMode) -> Result<RollOutput, RollError> {
+pub async fn run_rsl(#[cfg_attr(not(target_os = "windows"), allow(unused))] wsl_distro: Option<&str>, repo_path: &Path, rsl_version: &str, use_rust_cli: bool, supports_unsalted_seeds: bool, creates_log_by_default: bool, seeds: Seeds, preset: Option<&Either<String, serde_json::Map<String, serde_json::Value>>>, seed_idx: SeedIdx, output_mode: OutputMode, seed_timeout: Option<Duration>, memory_limit: Option<ByteSize>) -> Result<RollOutput, RollError> {
     let python = python().await?;
     #[cfg_attr(not(target_os = "windows"), allow(unused_mut))] let mut cmd_name = python.display().to_string();
     let (supports_plando_filename_base, supports_seed, supports_no_salt) = if let Some((_, major, minor, patch, supplementary)) = regex_captures!(r"^([0-9]+)\.([0-9]+)\.([0-9]+) Fenhl-([0-9]+)(?: riir-[0-9]+)?$", &rsl_version.trim()) {
@@ -598,14 +823,12 @@ pub async fn run_rsl(#[cfg_attr(not(target_os = "windows"), allow(unused))] wsl_
             }
         }
     }
-    cmd.stdin(Stdio::null());
     let mut input = None;
     if let Some(preset) = preset {
         match preset {
             Either::Left(name) => { cmd.arg(format!("--override=weights/{name}_override.json")); }
             Either::Right(weights) => {
                 cmd.arg("--override=-");
-                cmd.stdin(Stdio::piped());
                 input = Some(serde_json::to_vec(&weights)?);
             }
         }
@@ -614,18 +837,47 @@ pub async fn run_rsl(#[cfg_attr(not(target_os = "windows"), allow(unused))] wsl_
     if let Some(user_dirs) = UserDirs::new() {
         cmd.env("PATH", env::join_paths([user_dirs.home_dir().join(".cargo").join("bin"), PathBuf::from("/opt/homebrew/bin"), PathBuf::from("/usr/local/bin")].into_iter().chain(env::var_os("PATH").map(|path| env::split_paths(&path).collect_vec()).into_iter().flatten()))?);
     }
+    #[cfg(target_os = "linux")] if let Some(memory_limit) = memory_limit {
+        cmd = limit_memory(&cmd, &mut cmd_name, memory_limit);
+    }
+    cmd.stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() });
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    #[cfg(unix)] if seed_timeout.is_some() {
+        // allows killing subprocesses of the random settings script (or of `perf`) on timeout
+        cmd.process_group(0);
+    }
+    // the seed timeout covers both the random settings script and the randomizer
+    let deadline = seed_timeout.map(|seed_timeout| Instant::now() + seed_timeout);
     let mut process = cmd.spawn().at_command(cmd_name.clone())?;
     if let Some(input) = input {
-        process.stdin.as_mut().expect("piped stdin missing").write_all(&input).await.at_command(cmd_name.clone())?;
+        let mut stdin = process.stdin.take().expect("piped stdin missing");
+        stdin.write_all(&input).await.at_command(cmd_name.clone())?;
+    }
+    let (output, timed_out) = wait_until(process, &cmd_name, deadline).await?;
+    // allocations fail once the limit is reached, which Python reports as a MemoryError
+    let out_of_memory = cfg!(target_os = "linux") && memory_limit.is_some() && !output.status.success() && !timed_out && String::from_utf8_lossy(&output.stderr).lines().any(|line| regex_is_match!("^MemoryError(?::|$)", line));
+    if timed_out || out_of_memory {
+        // recorded the same way as the randomizer timing out or running out of memory
+        let stderr = Bytes::from(output.stderr);
+        return Ok(RollOutput {
+            instructions: Err(stderr.clone()),
+            rsl_instructions: Err(stderr.clone()),
+            log: Err(stderr),
+            patch: None,
+            rsl_plando: None,
+            exit_signal: {
+                #[cfg(unix)] { output.status.signal() }
+                #[cfg(not(unix))] { None }
+            },
+            timed_out, out_of_memory,
+        })
     }
-    let output = process.wait_with_output().await.at_command(cmd_name.clone())?;
     let stderr = BufRead::lines(&*output.stderr).try_collect::<_, Vec<_>, _>().at_command(cmd_name.clone())?;
     if output.status.success() || output.status.code() == Some(3) {
         let stdout = BufRead::lines(&*output.stdout).try_collect::<_, Vec<_>, _>().at_command(cmd_name)?;
         let plando_filename = stdout.iter().rev().find_map(|line| line.strip_prefix("Plando File: ")).ok_or_else(|| RollError::SpoilerLogPath(output.clone()))?;
-        let mut roll_output = run_rando(wsl_distro, &repo_path.join("randomizer"), &repo_path.join("data").join("oot-ntscu-1.0.n64"), use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, &RandoSettings::Default, &serde_json::Map::default(), Some(Path::new(&format!("../data/{plando_filename}"))), false, seed_idx, output_mode).await?;
+        let mut roll_output = run_rando(wsl_distro, &repo_path.join("randomizer"), &repo_path.join("data").join("oot-ntscu-1.0.n64"), use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, &RandoSettings::Default, &serde_json::Map::default(), Some(Path::new(&format!("../data/{plando_filename}"))), None, seed_idx, output_mode, deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())), memory_limit).await?;
         roll_output.rsl_plando = Some(repo_path.join("data").join(plando_filename));
         roll_output.rsl_instructions = if let OutputMode::Bench { .. } = output_mode {
             #[cfg(any(target_os = "linux", target_os = "windows"))] {
@@ -1,4 +1,5 @@
 use {
+    std::time::Duration,
     async_proto::Protocol,
     bytes::Bytes,
     bytesize::ByteSize,
@@ -6,6 +7,7 @@ use {
         OutputMode,
         RandoSetup,
         SeedIdx,
+        Side,
         worker::SupervisorMessage,
     },
 };
@@ -15,8 +17,11 @@ pub enum ClientMessage {
     Handshake {
         password: String,
         rando_rev: gix::ObjectId,
+        ab_rev: Option<gix::ObjectId>,
         setup: RandoSetup,
         output_mode: OutputMode,
+        seed_timeout: Option<Duration>,
+        memory_limit: Option<ByteSize>,
         min_disk: ByteSize,
         min_disk_percent: f64,
         min_disk_mount_points: Option<Vec<String>>,
@@ -36,6 +41,7 @@ pub enum ServerMessage {
     Init(String),
     Ready(u8),
     Success {
+        side: Side,
         seed_idx: SeedIdx,
         /// present if the `bench` parameter was set and `perf` output was parsed successfully.
         instructions: Result<u64, Bytes>,
@@ -45,11 +51,18 @@ pub enum ServerMessage {
         rsl_plando: Option<Bytes>,
     },
     Failure {
+        side: Side,
         seed_idx: SeedIdx,
         /// present if the `bench` parameter was set and `perf` output was parsed successfully.
         instructions: Result<u64, Bytes>,
         rsl_instructions: Result<u64, Bytes>,
         error_log: Bytes,
+        /// the signal which terminated the randomizer, if any.
+        exit_signal: Option<i32>,
+        /// whether the randomizer was killed for exceeding the seed timeout.
+        timed_out: bool,
+        /// whether the randomizer failed because it exceeded the memory limit.
+        out_of_memory: bool,
         rsl_plando: Option<Bytes>,
     },
     Error {
@@ -3,8 +3,14 @@ use {
         borrow::Cow,
         collections::HashMap,
         env,
+        ffi::OsStr,
+        iter,
         num::NonZero,
-        path::PathBuf,
+        path::{
+            Path,
+            PathBuf,
+        },
+        process::ExitStatus,
         sync::Arc,
         time::Duration,
     },
@@ -32,6 +38,7 @@ use {
         rng,
     },
     semver::Version,
+    serde_json::json,
     systemstat::{
         Platform as _,
         System,
@@ -55,19 +62,25 @@ use {
     },
     crate::{
         OutputMode,
+        RandoSettings,
         RandoSetup,
         RollOutput,
         SeedIdx,
+        Side,
         gitdir,
     },
 };
-#[cfg(unix)] use std::io;
+#[cfg(unix)] use std::{
+    io,
+    os::unix::fs::PermissionsExt as _,
+};
 #[cfg(windows)] use tokio::time::sleep;
 
 pub enum Message {
     Init(String),
     Ready(u8),
     Success {
+        side: Side,
         seed_idx: SeedIdx,
         /// present if the `bench` parameter was set and `perf` output was parsed successfully.
         instructions: Result<u64, Bytes>,
@@ -77,19 +90,26 @@ pub enum Message {
         rsl_plando: Option<Either<PathBuf, Bytes>>,
     },
     Failure {
+        side: Side,
         seed_idx: SeedIdx,
         /// present if the `bench` parameter was set and `perf` output was parsed successfully.
         instructions: Result<u64, Bytes>,
         rsl_instructions: Result<u64, Bytes>,
         error_log: Bytes,
+        /// the signal which terminated the randomizer, if any.
+        exit_signal: Option<i32>,
+        /// whether the randomizer was killed for exceeding the seed timeout.
+        timed_out: bool,
+        /// whether the randomizer failed because it exceeded the memory limit.
+        out_of_memory: bool,
         rsl_plando: Option<Either<PathBuf, Bytes>>,
     },
 }
 
 #[derive(Debug, Protocol)]
 pub enum SupervisorMessage {
-    Roll(SeedIdx),
-    Cancel(SeedIdx),
+    Roll(Side, SeedIdx),
+    Cancel(Side, SeedIdx),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -113,6 +133,10 @@ pub enum Error {
     #[error(transparent)] Wheel(#[from] wheel::Error),
     #[error("user folder not found")]
     MissingHomeDir,
+    #[error("supervisor requested a seed from the second revision of an A/B run, but no second revision was specified")]
+    NoAbRev,
+    #[error("the settings chosen by the random settings script for this seed were not recorded")]
+    NoRslPlando,
     #[error("failed to determine randomizer version from RSL script")]
     RslVersion,
 }
@@ -180,13 +204,22 @@ async fn wait_ready(min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_po
     Ok(if wait > Duration::default() { Some((wait, message)) } else { None })
 }
 
-pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiver<SupervisorMessage>, base_rom_path: PathBuf, cores: i8, wsl_distro: Option<String>, git_rev: gix::ObjectId, setup: RandoSetup, output_mode: OutputMode, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<&[PathBuf]>, priority_users: &[String], #[cfg_attr(not(windows), allow(unused))] race: bool) -> Result<(), Error> {
+/// A randomizer repository which has been cloned and built at a given revision.
+struct PreparedRepo {
+    path: PathBuf,
+    rsl_version: Option<String>,
+    use_rust_cli: bool,
+    supports_unsalted_seeds: bool,
+    creates_log_by_default: bool,
+}
+
+async fn prepare_repo(tx: &mpsc::Sender<Message>, base_rom_path: &Path, wsl_distro: Option<&str>, git_rev: gix::ObjectId, setup: &RandoSetup, output_mode: OutputMode) -> Result<PreparedRepo, Error> {
     let mut rsl_version = None;
     let mut use_rust_cli = false;
     let mut supports_unsalted_seeds = false;
     let mut creates_log_by_default = true;
-    let (rando_github_user, rando_repo_name, rando_git_rev, rando_repo_parent, rando_repo_dir_name, uncompressed_base_rom_tempfile, plando_tempfile) = match setup {
-        RandoSetup::Normal { ref github_user, ref repo, ref plando, .. } => {
+    let (rando_github_user, rando_repo_name, rando_git_rev, rando_repo_parent, rando_repo_dir_name) = match *setup {
+        RandoSetup::Normal { ref github_user, ref repo, .. } => {
             tx.send(Message::Init(format!("cloning randomizer: determining repo path"))).await?;
             (
                 Cow::Borrowed(&**github_user),
@@ -194,24 +227,6 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
                 git_rev,
                 gitdir().await?.join("github.com").join(github_user).join(repo).join("rev"),
                 git_rev.to_string(),
-                {
-                    tx.send(Message::Init(format!("decompressing base rom"))).await?;
-                    let tempfile = tempfile::Builder::new().prefix("oot_").suffix(".n64").tempfile().at_unknown()?;
-                    let mut base_rom = fs::read(&base_rom_path).await?;
-                    tokio::fs::File::from_std(tempfile.reopen().at(&tempfile)?).write_all(&if base_rom.len() == 0x0400_0000 {
-                        base_rom
-                    } else {
-                        decompress::decompress(&mut base_rom)?
-                    }).await.at(&tempfile)?;
-                    Some(tempfile.into_temp_path())
-                },
-                if plando.is_empty() {
-                    None
-                } else {
-                    let tempfile = tempfile::Builder::new().prefix("plando_").suffix(".json").tempfile().at_unknown()?;
-                    tokio::fs::File::from_std(tempfile.reopen().at(&tempfile)?).write_all(&serde_json::to_vec_pretty(plando)?).await.at(&tempfile)?;
-                    Some(tempfile.into_temp_path())
-                },
             )
         }
         RandoSetup::Rsl { ref github_user, ref repo, .. } => {
@@ -236,7 +251,7 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
             if !fs::exists(&rsl_base_rom_path).await? {
                 tx.send(Message::Init(format!("decompressing base rom"))).await?;
                 fs::create_dir_all(rsl_data_dir).await?;
-                fs::write(rsl_base_rom_path, decompress::decompress(&mut fs::read(&base_rom_path).await?)?).await?;
+                fs::write(rsl_base_rom_path, decompress::decompress(&mut fs::read(base_rom_path).await?)?).await?;
             }
             let python = crate::python().await?;
             rsl_version = Some(String::from_utf8(Command::new(&python)
@@ -263,7 +278,7 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
                 .check(python.display().to_string()).await?
                 .stdout
             )?;
-            (Cow::Owned(rando_github_user), Cow::Owned(rando_repo_name), randomizer_commit.parse()?, repo_path, format!("randomizer"), None, None)
+            (Cow::Owned(rando_github_user), Cow::Owned(rando_repo_name), randomizer_commit.parse()?, repo_path, format!("randomizer"))
         }
     };
     tx.send(Message::Init(format!("checking if randomizer repo exists"))).await?;
@@ -285,7 +300,7 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
         let cargo_command = || Ok::<_, Error>(if cfg!(target_os = "windows") && matches!(output_mode, OutputMode::Bench { .. }) {
             //TODO update Rust toolchain on WSL
             let mut cargo = Command::new(crate::WSL);
-            if let Some(wsl_distro) = &wsl_distro {
+            if let Some(wsl_distro) = wsl_distro {
                 cargo.arg("--distribution");
                 cargo.arg(wsl_distro);
             }
@@ -314,7 +329,7 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
             #[cfg(target_os = "windows")] {
                 if let OutputMode::Bench { .. } = output_mode {
                     let mut cp = Command::new(crate::WSL);
-                    if let Some(wsl_distro) = &wsl_distro {
+                    if let Some(wsl_distro) = wsl_distro {
                         cp.arg("--distribution");
                         cp.arg(wsl_distro);
                     }
@@ -369,7 +384,7 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
         tx.send(Message::Init(format!("compiling Python code with mypyc"))).await?;
         let mut mypyc = if cfg!(target_os = "windows") && matches!(output_mode, OutputMode::Bench { .. }) {
             let mut mypyc = Command::new(crate::WSL);
-            if let Some(wsl_distro) = &wsl_distro {
+            if let Some(wsl_distro) = wsl_distro {
                 mypyc.arg("--distribution");
                 mypyc.arg(wsl_distro);
             }
@@ -380,9 +395,43 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
         };
         mypyc.current_dir(&rando_repo_path).check("mypyc").await?;
     }
-    let repo_path = match setup {
-        RandoSetup::Normal { .. } => rando_repo_path,
-        RandoSetup::Rsl { .. } => rando_repo_parent,
+    Ok(PreparedRepo {
+        path: match setup {
+            RandoSetup::Normal { .. } => rando_repo_path,
+            RandoSetup::Rsl { .. } => rando_repo_parent,
+        },
+        rsl_version, use_rust_cli, supports_unsalted_seeds, creates_log_by_default,
+    })
+}
+
+pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiver<SupervisorMessage>, base_rom_path: PathBuf, cores: i8, wsl_distro: Option<String>, git_rev: gix::ObjectId, ab_rev: Option<gix::ObjectId>, setup: RandoSetup, output_mode: OutputMode, seed_timeout: Option<Duration>, memory_limit: Option<ByteSize>, min_disk: ByteSize, min_disk_percent: f64, min_disk_mount_points: Option<&[PathBuf]>, priority_users: &[String], #[cfg_attr(not(windows), allow(unused))] race: bool) -> Result<(), Error> {
+    let (uncompressed_base_rom_tempfile, plando_tempfile) = if let RandoSetup::Normal { ref plando, .. } = setup {
+        tx.send(Message::Init(format!("decompressing base rom"))).await?;
+        let uncompressed_base_rom_tempfile = tempfile::Builder::new().prefix("oot_").suffix(".n64").tempfile().at_unknown()?;
+        let mut base_rom = fs::read(&base_rom_path).await?;
+        tokio::fs::File::from_std(uncompressed_base_rom_tempfile.reopen().at(&uncompressed_base_rom_tempfile)?).write_all(&if base_rom.len() == 0x0400_0000 {
+            base_rom
+        } else {
+            decompress::decompress(&mut base_rom)?
+        }).await.at(&uncompressed_base_rom_tempfile)?;
+        (
+            Some(uncompressed_base_rom_tempfile.into_temp_path()),
+            if plando.is_empty() {
+                None
+            } else {
+                let tempfile = tempfile::Builder::new().prefix("plando_").suffix(".json").tempfile().at_unknown()?;
+                tokio::fs::File::from_std(tempfile.reopen().at(&tempfile)?).write_all(&serde_json::to_vec_pretty(plando)?).await.at(&tempfile)?;
+                Some(tempfile.into_temp_path())
+            },
+        )
+    } else {
+        (None, None)
+    };
+    let repo_a = prepare_repo(&tx, &base_rom_path, wsl_distro.as_deref(), git_rev, &setup, output_mode).await?;
+    let repo_b = if let Some(ab_rev) = ab_rev {
+        Some(prepare_repo(&tx, &base_rom_path, wsl_distro.as_deref(), ab_rev, &setup, output_mode).await?)
+    } else {
+        None
     };
     let mut msg_buf = Vec::default();
     'wait_ready: while let Some((duration, reason)) = wait_ready(min_disk, min_disk_percent, min_disk_mount_points, priority_users).await? {
@@ -432,53 +481,61 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
     };
     let mut recheck_ready_at = None;
     let mut waiting_cores = 0;
-    let handle_seed = |seed_idx| {
+    let handle_seed = |side, seed_idx| {
+        let PreparedRepo { ref path, ref rsl_version, use_rust_cli, supports_unsalted_seeds, creates_log_by_default } = *match side {
+            Side::A => &repo_a,
+            Side::B => repo_b.as_ref().ok_or(Error::NoAbRev)?,
+        };
         let run_future = match setup {
-            RandoSetup::Normal { ref settings, ref json_settings, world_counts, ref seeds, .. } => {
+            RandoSetup::Normal { ref settings, ref json_settings, ref world_counts, ref sweeps, ref seeds, .. } => {
+                let world_count = world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx));
                 let wsl_distro = wsl_distro.clone();
-                let repo_path = repo_path.clone();
+                let repo_path = path.clone();
                 let seeds = seeds.clone();
                 let settings = settings.clone();
-                let json_settings = json_settings.clone();
+                let mut json_settings = json_settings.clone();
+                if !sweeps.is_empty() {
+                    json_settings.extend(crate::sweep_point(sweeps, crate::sweep_point_idx(sweeps, seed_idx)));
+                }
                 let uncompressed_base_rom_path = uncompressed_base_rom_tempfile.as_ref().expect("missing uncompressed base rom").to_path_buf();
                 let plando = plando_tempfile.as_ref().map(|tempfile| tempfile.to_path_buf());
-                Either::Left(async move { crate::run_rando(wsl_distro.as_deref(), &repo_path, &uncompressed_base_rom_path, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, &settings, &json_settings, plando.as_deref(), world_counts, seed_idx, output_mode).await })
+                Either::Left(async move { crate::run_rando(wsl_distro.as_deref(), &repo_path, &uncompressed_base_rom_path, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, &settings, &json_settings, plando.as_deref(), world_count, seed_idx, output_mode, seed_timeout, memory_limit).await })
             }
             RandoSetup::Rsl { ref preset, ref seeds, .. } => {
                 let wsl_distro = wsl_distro.clone();
-                let repo_path = repo_path.clone();
+                let repo_path = path.clone();
                 let rsl_version = rsl_version.clone().unwrap();
                 let seeds = seeds.clone();
                 let preset = preset.clone();
-                Either::Right(async move { crate::run_rsl(wsl_distro.as_deref(), &repo_path, &rsl_version, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, preset.as_ref(), seed_idx, output_mode).await })
+                Either::Right(async move { crate::run_rsl(wsl_distro.as_deref(), &repo_path, &rsl_version, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds, preset.as_ref(), seed_idx, output_mode, seed_timeout, memory_limit).await })
             }
         };
         let tx = tx.clone();
         let wsl_distro = wsl_distro.clone();
-        tokio::spawn(async move {
+        Ok::<_, Error>(tokio::spawn(async move {
             tx.send(match run_future.await? {
-                RollOutput { instructions, rsl_instructions, log: Ok(spoiler_log_path), patch, rsl_plando } => Message::Success {
+                RollOutput { instructions, rsl_instructions, log: Ok(spoiler_log_path), patch, rsl_plando, exit_signal: _, timed_out: _, out_of_memory: _ } => Message::Success {
                     spoiler_log: Either::Left(spoiler_log_path),
                     patch: patch.map(|(is_wsl, patch)| Either::Left((is_wsl.then(|| wsl_distro.clone()), patch))),
                     rsl_plando: rsl_plando.map(Either::Left),
-                    seed_idx, instructions, rsl_instructions,
+                    side, seed_idx, instructions, rsl_instructions,
                 },
-                RollOutput { instructions, rsl_instructions, log: Err(error_log), patch: _, rsl_plando } => Message::Failure {
+                RollOutput { instructions, rsl_instructions, log: Err(error_log), patch: _, rsl_plando, exit_signal, timed_out, out_of_memory } => Message::Failure {
                     rsl_plando: rsl_plando.map(Either::Left),
-                    seed_idx, instructions, rsl_instructions, error_log,
+                    side, seed_idx, instructions, rsl_instructions, error_log, exit_signal, timed_out, out_of_memory,
                 },
             }).await?;
             Ok::<_, Error>(())
-        })
+        }))
     };
     for msg in msg_buf {
         match msg {
-            SupervisorMessage::Roll(seed_idx) => {
-                let rando_task = handle_seed(seed_idx);
-                abort_handles.write().entry(seed_idx).or_default().push(rando_task.abort_handle());
+            SupervisorMessage::Roll(side, seed_idx) => {
+                let rando_task = handle_seed(side, seed_idx)?;
+                abort_handles.write().entry((side, seed_idx)).or_default().push(rando_task.abort_handle());
                 rando_tasks.push(rando_task);
             }
-            SupervisorMessage::Cancel(seed_idx) => for abort_handle in abort_handles.read().get(&seed_idx).into_iter().flatten() {
+            SupervisorMessage::Cancel(side, seed_idx) => for abort_handle in abort_handles.read().get(&(side, seed_idx)).into_iter().flatten() {
                 abort_handle.abort();
             },
         }
@@ -531,13 +588,13 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
             msg = rx.recv(), if !rx_is_closed => if let Some(msg) = msg {
                 if verbose { println!("work() got supervisor message: {msg:?}") }
                 match msg {
-                    SupervisorMessage::Roll(seed_idx) => {
-                        let rando_task = handle_seed(seed_idx);
-                        abort_handles.write().entry(seed_idx).or_default().push(rando_task.abort_handle());
+                    SupervisorMessage::Roll(side, seed_idx) => {
+                        let rando_task = handle_seed(side, seed_idx)?;
+                        abort_handles.write().entry((side, seed_idx)).or_default().push(rando_task.abort_handle());
                         rando_tasks.push(rando_task);
                     }
-                    SupervisorMessage::Cancel(seed_idx) => {
-                        for abort_handle in abort_handles.read().get(&seed_idx).into_iter().flatten() {
+                    SupervisorMessage::Cancel(side, seed_idx) => {
+                        for abort_handle in abort_handles.read().get(&(side, seed_idx)).into_iter().flatten() {
                             abort_handle.abort();
                         }
                     }
@@ -562,3 +619,86 @@ pub async fn work(verbose: bool, tx: mpsc::Sender<Message>, mut rx: mpsc::Receiv
     if verbose { println!("end of work()") }
     Ok(())
 }
+
+/// Quotes a string for use as a single word in a POSIX shell script.
+fn sh_quote(s: &OsStr) -> String {
+    format!("'{}'", s.to_string_lossy().replace('\'', "'\\''"))
+}
+
+/// A shell script which runs the given command with the contents of the given file as its stdin.
+fn shell_script(cmd: &Command, input_path: &Path) -> String {
+    let cmd = cmd.as_std();
+    let mut script = format!("#!/bin/sh\n\n");
+    if let Some(current_dir) = cmd.get_current_dir() {
+        script.push_str(&format!("cd {} || exit\n", sh_quote(current_dir.as_os_str())));
+    }
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            script.push_str(&format!("export {}={}\n", key.to_string_lossy(), sh_quote(value)));
+        }
+    }
+    script.push_str(&format!(
+        "exec {} < {}\n",
+        iter::once(cmd.get_program()).chain(cmd.get_args()).map(sh_quote).format(" "),
+        sh_quote(input_path.as_os_str()),
+    ));
+    script
+}
+
+/// Rolls a single seed on this computer the same way [`work`] would, but keeps all of the randomizer's output in `out_dir`.
+///
+/// Along with the spoiler log, patch file, and compressed ROM, this writes the settings passed to the randomizer to `settings.json`, its stdout and stderr to `stdout.txt` and `stderr.txt`, and a shell script which rolls the seed again to `repro.sh`.
+///
+/// For the random settings script, `rsl_plando` must be the plando file it generated when the seed was originally rolled, since the script isn't rerun.
+pub async fn repro(tx: &mpsc::Sender<Message>, base_rom_path: &Path, wsl_distro: Option<&str>, git_rev: gix::ObjectId, setup: &RandoSetup, seed_idx: SeedIdx, rsl_plando: Option<&Path>, out_dir: &Path) -> Result<ExitStatus, Error> {
+    let output_mode = OutputMode::Normal { patch: true };
+    fs::create_dir_all(out_dir).await?;
+    let PreparedRepo { path, rsl_version: _, use_rust_cli, supports_unsalted_seeds, creates_log_by_default } = prepare_repo(tx, base_rom_path, wsl_distro, git_rev, setup, output_mode).await?;
+    let mut artifact_settings = serde_json::Map::default();
+    artifact_settings.insert(format!("output_dir"), json!(out_dir));
+    artifact_settings.insert(format!("create_compressed_rom"), json!(true));
+    artifact_settings.insert(format!("create_patch_file"), json!(true));
+    let (mut cmd, cmd_name, input) = match *setup {
+        RandoSetup::Normal { ref settings, ref json_settings, ref plando, ref world_counts, ref sweeps, ref seeds, .. } => {
+            tx.send(Message::Init(format!("decompressing base rom"))).await?;
+            let uncompressed_base_rom_path = out_dir.join("oot-ntscu-1.0.n64");
+            let mut base_rom = fs::read(base_rom_path).await?;
+            fs::write(&uncompressed_base_rom_path, if base_rom.len() == 0x0400_0000 {
+                base_rom
+            } else {
+                decompress::decompress(&mut base_rom)?
+            }).await?;
+            let plando_path = if plando.is_empty() {
+                None
+            } else {
+                let plando_path = out_dir.join("plando.json");
+                fs::write_json(&plando_path, plando).await?;
+                Some(plando_path)
+            };
+            let mut json_settings = json_settings.clone();
+            if !sweeps.is_empty() {
+                json_settings.extend(crate::sweep_point(sweeps, crate::sweep_point_idx(sweeps, seed_idx)));
+            }
+            json_settings.extend(artifact_settings);
+            let world_count = world_counts.as_ref().and_then(|world_counts| world_counts.world_count(seed_idx));
+            crate::rando_command(wsl_distro, &path, &uncompressed_base_rom_path, use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds.clone(), settings, &json_settings, plando_path.as_deref(), world_count, seed_idx, output_mode).await?
+        }
+        RandoSetup::Rsl { ref seeds, .. } => {
+            let plando_path = out_dir.join("random_settings.json");
+            fs::copy(rsl_plando.ok_or(Error::NoRslPlando)?, &plando_path).await?;
+            crate::rando_command(wsl_distro, &path.join("randomizer"), &path.join("data").join("oot-ntscu-1.0.n64"), use_rust_cli, supports_unsalted_seeds, creates_log_by_default, seeds.clone(), &RandoSettings::Default, &artifact_settings, Some(&plando_path), None, seed_idx, output_mode).await?
+        }
+    };
+    let input_path = out_dir.join("settings.json");
+    fs::write(&input_path, input).await?;
+    let script_path = out_dir.join("repro.sh");
+    fs::write(&script_path, shell_script(&cmd, &input_path)).await?;
+    #[cfg(unix)] tokio::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).await.at(&script_path)?;
+    tx.send(Message::Init(format!("rolling seed"))).await?;
+    let stdout_path = out_dir.join("stdout.txt");
+    let stderr_path = out_dir.join("stderr.txt");
+    cmd.stdin(std::fs::File::open(&input_path).at(&input_path)?);
+    cmd.stdout(std::fs::File::create(&stdout_path).at(&stdout_path)?);
+    cmd.stderr(std::fs::File::create(&stderr_path).at(&stderr_path)?);
+    Ok(cmd.status().await.at_command(cmd_name)?)
+}